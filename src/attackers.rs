@@ -0,0 +1,174 @@
+//! Attack queries, which squares are attacked and by which pieces.
+
+use crate::attacks;
+use crate::bitboard::color_index;
+use crate::bitboard::piece_index;
+use crate::BitBoard;
+use crate::Board;
+use crate::Color;
+use crate::PieceType;
+use crate::Square;
+
+/// Pieces of [Color] attacking [Square] in position given by piece and color bitboards.
+/// Used also for positions which aren't on any [Board] (e.g. after move, when checking legality).
+pub(crate) fn attackers_of(
+    pieces: &[BitBoard; 6],
+    colors: &[BitBoard; 2],
+    sq: Square,
+    by: Color,
+) -> BitBoard {
+    let occupied = colors[0] | colors[1];
+    let queens = pieces[piece_index(PieceType::Queen)];
+
+    // pawn of `by` attacks square, if pawn of other color on that square would attack it
+    (attacks::pawn(by.get_inverse(), sq) & pieces[piece_index(PieceType::Pawn)]
+        | attacks::knight(sq) & pieces[piece_index(PieceType::Knight)]
+        | attacks::king(sq) & pieces[piece_index(PieceType::King)]
+        | attacks::rook(sq, occupied) & (pieces[piece_index(PieceType::Rook)] | queens)
+        | attacks::bishop(sq, occupied) & (pieces[piece_index(PieceType::Bishop)] | queens))
+        & colors[color_index(by)]
+}
+
+impl Board {
+    /// Get [Square]s of all pieces of [Color] which attack [Square].
+    /// Square doesn't have to be occupied (pawns attack diagonally even empty squares).
+    pub fn attackers(&self, sq: Square, by: Color) -> Vec<Square> {
+        attackers_of(&self.pieces, &self.colors, sq, by)
+            .iter()
+            .collect()
+    }
+
+    /// Check if [Square] is attacked by any piece of [Color].
+    /// Square doesn't have to be occupied (pawns attack diagonally even empty squares).
+    pub fn is_square_attacked(&self, sq: Square, by: Color) -> bool {
+        !attackers_of(&self.pieces, &self.colors, sq, by).is_empty()
+    }
+
+    /// Create attack map of [Color], number of attacking pieces for every [Square].
+    /// Map is indexed same as [Square] (map[sq.0]).
+    pub fn attack_map(&self, by: Color) -> [u8; 64] {
+        let mut map = [0; 64];
+        for (i, count) in map.iter_mut().enumerate() {
+            *count = attackers_of(&self.pieces, &self.colors, Square(i), by).count() as u8;
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Piece;
+
+    /// Attackers of [Square] found by walking from every piece of [Color], without [BitBoard]s.
+    fn brute_force(board: &Board, sq: Square, by: Color) -> Vec<Square> {
+        let (row, col) = ((sq.0 / 8) as i32, (sq.0 % 8) as i32);
+        let at = |r: i32, c: i32| -> Option<Piece> {
+            if (0..8).contains(&r) && (0..8).contains(&c) {
+                *board.get(Square((r * 8 + c) as usize))
+            } else {
+                None
+            }
+        };
+
+        let mut found = Vec::new();
+        for i in 0..64 {
+            let piece = match *board.get(Square(i)) {
+                Some(piece) if piece.color == by => piece,
+                _ => continue,
+            };
+            let (dr, dc) = (row - (i / 8) as i32, col - (i % 8) as i32);
+            let slides = |dirs: &[(i32, i32)]| {
+                dirs.iter().any(|&(r, c)| {
+                    (1..8).any(|n| {
+                        (r * n, c * n) == (dr, dc)
+                            && (1..n)
+                                .all(|k| at(row - r * n + r * k, col - c * n + c * k).is_none())
+                    })
+                })
+            };
+            let straight = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+            let diagonal = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+            let forward = if by == Color::White { -1 } else { 1 };
+            let attacks = match piece.piece_type {
+                PieceType::Pawn => dr == forward && dc.abs() == 1,
+                PieceType::Knight => dr.abs() * dc.abs() == 2,
+                PieceType::King => dr.abs().max(dc.abs()) == 1,
+                PieceType::Rook => slides(&straight),
+                PieceType::Bishop => slides(&diagonal),
+                PieceType::Queen => slides(&straight) || slides(&diagonal),
+            };
+            if attacks {
+                found.push(Square(i));
+            }
+        }
+        found
+    }
+
+    #[test]
+    fn pawns_and_kings() {
+        let board = Board::from_fen("4k3/8/8/3p4/8/8/4P3/4K3 w - - 0 1").unwrap();
+        // pawns attack empty squares diagonally, not square in front
+        assert_eq!(board.attackers(Square::D3, Color::White), vec![Square::E2]);
+        assert_eq!(board.attackers(Square::F3, Color::White), vec![Square::E2]);
+        assert!(!board.is_square_attacked(Square::E3, Color::White));
+        assert_eq!(board.attackers(Square::E4, Color::Black), vec![Square::D5]);
+        assert!(!board.is_square_attacked(Square::D4, Color::Black));
+
+        assert_eq!(board.attackers(Square::D2, Color::White), vec![Square::E1]);
+        assert!(board.is_square_attacked(Square::F7, Color::Black));
+        assert!(!board.is_square_attacked(Square::E6, Color::Black));
+        let map = board.attack_map(Color::White);
+        assert_eq!(map[Square::D2.0], 1);
+        assert_eq!(map[Square::F2.0], 1);
+        assert_eq!(map[Square::E3.0], 0);
+    }
+
+    #[test]
+    fn sliders_are_blocked() {
+        let board = Board::from_fen("4k3/8/8/8/1b6/8/3N4/R3K2R w - - 0 1").unwrap();
+        // rook on h1 doesn't see through king, bishop doesn't see through knight
+        assert_eq!(
+            board.attackers(Square::F1, Color::White),
+            vec![Square::D2, Square::E1, Square::H1]
+        );
+        assert!(!board
+            .attackers(Square::C1, Color::White)
+            .contains(&Square::H1));
+        assert_eq!(
+            board.attackers(Square::D1, Color::White),
+            vec![Square::A1, Square::E1]
+        );
+        assert!(!board.is_square_attacked(Square::D1, Color::Black));
+        assert_eq!(board.attackers(Square::D2, Color::Black), vec![Square::B4]);
+        assert!(!board.is_square_attacked(Square::E1, Color::Black));
+        assert_eq!(board.attack_map(Color::White)[Square::C1.0], 1);
+        assert!(!board.is_square_attacked(Square::A2, Color::Black));
+    }
+
+    #[test]
+    fn compare_with_brute_force() {
+        for fen in [
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            "r1bq1rk1/pp2nppp/2n1p3/2ppP3/3P4/P1PB1N2/2P2PPP/R1BQK2R b KQ - 1 8",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            for by in [Color::White, Color::Black] {
+                let map = board.attack_map(by);
+                for (i, &count) in map.iter().enumerate() {
+                    let sq = Square(i);
+                    let expected = brute_force(&board, sq, by);
+                    assert_eq!(board.attackers(sq, by), expected, "{} {:?} {}", fen, by, sq);
+                    assert_eq!(board.is_square_attacked(sq, by), !expected.is_empty());
+                    assert_eq!(count as usize, expected.len());
+                    assert_eq!(
+                        attackers_of(&board.pieces, &board.colors, sq, by).count() as usize,
+                        expected.len()
+                    );
+                }
+            }
+        }
+    }
+}
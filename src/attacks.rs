@@ -0,0 +1,379 @@
+//! Precomputed attack tables of every piece, e.g. `attacks::rook(sq, occupied)`.
+//! Knight, king and pawn attacks are looked up by [Square], sliding pieces use magic bitboards.
+//! Tables are generated on first use.
+
+use std::sync::OnceLock;
+
+use crate::bitboard::color_index;
+use crate::BitBoard;
+use crate::Color;
+use crate::Square;
+
+/// Directions (rank, file) of rook rays.
+const ROOK_DIRS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+/// Directions (rank, file) of bishop rays.
+const BISHOP_DIRS: [(i32, i32); 4] = [(-1, -1), (-1, 1), (1, -1), (1, 1)];
+/// Knight jumps (rank, file).
+const KNIGHT_JUMPS: [(i32, i32); 8] = [
+    (-2, -1),
+    (-2, 1),
+    (-1, -2),
+    (-1, 2),
+    (1, -2),
+    (1, 2),
+    (2, -1),
+    (2, 1),
+];
+/// King steps (rank, file).
+const KING_STEPS: [(i32, i32); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+/// Magic numbers of rook for every [Square], found by searching random sparse numbers.
+const ROOK_MAGICS: [u64; 64] = [
+    0x2080002080400010,
+    0x00c0002001401000,
+    0x2100110008402002,
+    0x0880080081041000,
+    0x0200020020041008,
+    0x2300040008010012,
+    0x0c00283004008201,
+    0x0180010000407a80,
+    0x0168800080400020,
+    0x0010400040201000,
+    0x1001002001001048,
+    0x1001002408100100,
+    0x0801000408010012,
+    0x4001000209000400,
+    0x08a20004c8020001,
+    0x2002801145002280,
+    0x0080860021004200,
+    0x001000c009402002,
+    0x00b0002004002800,
+    0x100a808010020800,
+    0x9400808004000800,
+    0x0090808004000200,
+    0x0000040010810208,
+    0x2000020000448534,
+    0x4104400480008033,
+    0x0000810100204000,
+    0x0440430900200010,
+    0x4600240900100100,
+    0x0804080100110004,
+    0x0001000300080400,
+    0x0004084400011002,
+    0x0023040200008041,
+    0x0580050043002080,
+    0x0400804002802008,
+    0x0001002001004010,
+    0x0080200a02001040,
+    0x600d480280802400,
+    0x400b800201800c00,
+    0x2408211004004208,
+    0x0200211082000844,
+    0x0020804010208000,
+    0x5030004020104000,
+    0xa042084080220010,
+    0x4088080010008080,
+    0x5002080100110004,
+    0x2012002010040400,
+    0x0040318210440008,
+    0x0120941040820001,
+    0x1000800100402100,
+    0x0040002010004840,
+    0x8108450020001900,
+    0x0200204008120200,
+    0x0080800c00180180,
+    0x0885000400420900,
+    0x230802011008c400,
+    0x3801740891432200,
+    0x0a00250212024082,
+    0x0000882040001105,
+    0x0042102082000a42,
+    0xc401210810000501,
+    0x0241001002480005,
+    0x0081000400880241,
+    0x0000009008024124,
+    0x0048122980410402,
+];
+/// Magic numbers of bishop for every [Square].
+const BISHOP_MAGICS: [u64; 64] = [
+    0x1862221006220044,
+    0x2104a14202020060,
+    0x2804081220444001,
+    0x2102408900010001,
+    0x0002021000040002,
+    0x08c3100805004300,
+    0x1084040124920050,
+    0x8900440043382010,
+    0x2401410802140040,
+    0x0901200454208020,
+    0x0000090216020541,
+    0x1283844040800804,
+    0x0521840420000803,
+    0x0800010402400c40,
+    0x0000408e10100404,
+    0x0009810048420800,
+    0x2004211004286808,
+    0x13080a1001380080,
+    0x0008801004220020,
+    0x0024000802480800,
+    0x1461001190400401,
+    0x0020400200500440,
+    0x0003000409019000,
+    0x000c20820d011802,
+    0x000804002164100c,
+    0x00048400a0011404,
+    0x5018110308044100,
+    0x0048a00804010020,
+    0x0007840000802000,
+    0x8808a20075004220,
+    0x8014040000822100,
+    0x110c03000e251101,
+    0x0081094820202010,
+    0x0008041000044100,
+    0x00c1202808940800,
+    0x8108100821040400,
+    0x1240010010010041,
+    0x0810004080011000,
+    0x00a20c0401804a00,
+    0x40014c0020050500,
+    0x5805082012042480,
+    0x2004022144031000,
+    0x2082002024204808,
+    0x0800004200800800,
+    0x0410020204100a02,
+    0x80c1204080804101,
+    0x0010104e01800042,
+    0x000800810c400208,
+    0x100080b008201210,
+    0x8000440605112101,
+    0x000202008c440040,
+    0x9004002210442200,
+    0x2032014088222045,
+    0x0c00202222c20000,
+    0x0140040820a50100,
+    0x0222104c29024018,
+    0x0200110121202004,
+    0x0800104200b00802,
+    0x0000401424020801,
+    0x4000000004208840,
+    0x0802e00040104100,
+    0x03000020a0424080,
+    0x0011c00408188121,
+    0x0848020822040013,
+];
+
+/// Magic of one [Square], maps relevant occupancy to index into attack table.
+#[derive(Clone, Copy, Default)]
+struct Magic {
+    /// Squares which can block the slider (board edges aren't included).
+    mask: u64,
+    magic: u64,
+    shift: u32,
+    /// Start of this [Square] attacks in shared table.
+    offset: usize,
+}
+
+impl Magic {
+    fn index(&self, occupied: BitBoard) -> usize {
+        self.offset + ((occupied.0 & self.mask).wrapping_mul(self.magic) >> self.shift) as usize
+    }
+}
+
+/// All attack tables.
+struct Tables {
+    pawn: [[BitBoard; 64]; 2],
+    knight: [BitBoard; 64],
+    king: [BitBoard; 64],
+    rook: [Magic; 64],
+    bishop: [Magic; 64],
+    /// Attacks of sliding pieces for every [Square] and relevant occupancy.
+    sliding: Vec<BitBoard>,
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(Tables::new)
+}
+
+/// Squares attacked by pawn of [Color] standing on [Square].
+pub fn pawn(color: Color, sq: Square) -> BitBoard {
+    tables().pawn[color_index(color)][sq.0]
+}
+
+/// Squares attacked by knight standing on [Square].
+pub fn knight(sq: Square) -> BitBoard {
+    tables().knight[sq.0]
+}
+
+/// Squares attacked by king standing on [Square].
+pub fn king(sq: Square) -> BitBoard {
+    tables().king[sq.0]
+}
+
+/// Squares attacked by rook standing on [Square], `occupied` squares are blocking it.
+/// First blocking square in every direction is attacked (it can be own piece).
+pub fn rook(sq: Square, occupied: BitBoard) -> BitBoard {
+    let t = tables();
+    t.sliding[t.rook[sq.0].index(occupied)]
+}
+
+/// Squares attacked by bishop standing on [Square], `occupied` squares are blocking it.
+/// First blocking square in every direction is attacked (it can be own piece).
+pub fn bishop(sq: Square, occupied: BitBoard) -> BitBoard {
+    let t = tables();
+    t.sliding[t.bishop[sq.0].index(occupied)]
+}
+
+/// Squares attacked by queen standing on [Square], `occupied` squares are blocking it.
+pub fn queen(sq: Square, occupied: BitBoard) -> BitBoard {
+    rook(sq, occupied) | bishop(sq, occupied)
+}
+
+/// [Square] shifted by (rank, file) offset, [None] if it's outside the [crate::Board].
+/// Rank offset is in [Square] index direction (positive goes toward first rank).
+fn offset(sq: Square, dr: i32, df: i32) -> Option<Square> {
+    let (r, f) = ((sq.0 / 8) as i32 + dr, (sq.0 % 8) as i32 + df);
+    if (0..8).contains(&r) && (0..8).contains(&f) {
+        Some(Square((r * 8 + f) as usize))
+    } else {
+        None
+    }
+}
+
+/// Squares reachable by single step (or jump) in every direction.
+fn step_attacks(sq: Square, steps: &[(i32, i32)]) -> BitBoard {
+    let mut attacks = BitBoard::EMPTY;
+    for (dr, df) in steps {
+        if let Some(to) = offset(sq, *dr, *df) {
+            attacks |= BitBoard::from_square(to);
+        }
+    }
+    attacks
+}
+
+/// Squares reachable by sliding in every direction, ray stops on first occupied square (included).
+/// Slow, used only for generating tables.
+fn ray_attacks(sq: Square, occupied: BitBoard, dirs: &[(i32, i32)]) -> BitBoard {
+    let mut attacks = BitBoard::EMPTY;
+    for (dr, df) in dirs {
+        let mut cur = sq;
+        while let Some(to) = offset(cur, *dr, *df) {
+            attacks |= BitBoard::from_square(to);
+            if occupied.contains(to) {
+                break;
+            }
+            cur = to;
+        }
+    }
+    attacks
+}
+
+/// Squares which can block rays, last square of every ray is left out (nothing is behind it).
+fn relevant_mask(sq: Square, dirs: &[(i32, i32)]) -> u64 {
+    let mut mask = 0;
+    for (dr, df) in dirs {
+        let mut cur = sq;
+        while let Some(to) = offset(cur, *dr, *df) {
+            if offset(to, *dr, *df).is_none() {
+                break;
+            }
+            mask |= 1 << to.0;
+            cur = to;
+        }
+    }
+    mask
+}
+
+impl Tables {
+    fn new() -> Tables {
+        let mut tables = Tables {
+            pawn: [[BitBoard::EMPTY; 64]; 2],
+            knight: [BitBoard::EMPTY; 64],
+            king: [BitBoard::EMPTY; 64],
+            rook: [Magic::default(); 64],
+            bishop: [Magic::default(); 64],
+            sliding: Vec::new(),
+        };
+
+        for i in 0..64 {
+            let sq = Square(i);
+            // white pawns attack toward lower indexes
+            tables.pawn[color_index(Color::White)][i] = step_attacks(sq, &[(-1, -1), (-1, 1)]);
+            tables.pawn[color_index(Color::Black)][i] = step_attacks(sq, &[(1, -1), (1, 1)]);
+            tables.knight[i] = step_attacks(sq, &KNIGHT_JUMPS);
+            tables.king[i] = step_attacks(sq, &KING_STEPS);
+            tables.rook[i] = init_magic(sq, &ROOK_DIRS, ROOK_MAGICS[i], &mut tables.sliding);
+            tables.bishop[i] = init_magic(sq, &BISHOP_DIRS, BISHOP_MAGICS[i], &mut tables.sliding);
+        }
+
+        tables
+    }
+}
+
+/// Create [Magic] for slider on [Square] and append its attacks to `sliding` table.
+fn init_magic(sq: Square, dirs: &[(i32, i32)], magic: u64, sliding: &mut Vec<BitBoard>) -> Magic {
+    let mask = relevant_mask(sq, dirs);
+    let bits = mask.count_ones();
+    let magic = Magic {
+        mask,
+        magic,
+        shift: 64 - bits,
+        offset: sliding.len(),
+    };
+    sliding.resize(sliding.len() + (1 << bits), BitBoard::EMPTY);
+
+    // every subset of mask (carry-rippler trick)
+    let mut subset: u64 = 0;
+    loop {
+        let attacks = ray_attacks(sq, BitBoard(subset), dirs);
+        let entry = &mut sliding[magic.index(BitBoard(subset))];
+        // magic is good only if different attacks never share index
+        debug_assert!(entry.is_empty() || *entry == attacks);
+        *entry = attacks;
+
+        subset = subset.wrapping_sub(mask) & mask;
+        if subset == 0 {
+            break;
+        }
+    }
+
+    magic
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sliders_match_ray_walking() {
+        // xorshift, sparse numbers are closer to real positions
+        let mut x: u64 = 1;
+        let mut next = || {
+            x ^= x << 13;
+            x ^= x >> 7;
+            x ^= x << 17;
+            x
+        };
+        for _ in 0..1000 {
+            let occupied = BitBoard(next() & next());
+            for i in 0..64 {
+                let sq = Square(i);
+                assert_eq!(rook(sq, occupied), ray_attacks(sq, occupied, &ROOK_DIRS));
+                assert_eq!(
+                    bishop(sq, occupied),
+                    ray_attacks(sq, occupied, &BISHOP_DIRS)
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Play chess in terminal, two humans or human against computer.
+//!
+//! Usage: `simple_chess [random|simple] [white|black]`, computer plays given color
+//! (black by deafult). Without arguments both sides are played by humans.
+//! Moves are written in SAN ("Nf3", "O-O") or UCI ("g1f3", "e1g1"), type `help` for commands.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::io::BufRead;
+use std::io::Write;
+
+use simple_chess::*;
+
+const HELP: &str = "Moves in SAN (Nf3, exd5, O-O, e8=Q) or UCI (g1f3, e7e8q).
+Commands:
+  moves  list legal moves
+  undo   take back last move (and move of computer)
+  flip   render board from other side
+  fen    print FEN of position
+  pgn    print game in PGN
+  help   print this help
+  quit   end program";
+
+/// How computer chooses its moves.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Opponent {
+    /// Any legal move.
+    Random,
+    /// Mate if possible, otherwise best capture which doesn't hang moved piece.
+    Simple,
+}
+
+/// Command typed by human.
+#[derive(Clone, Debug, PartialEq)]
+enum Command {
+    Empty,
+    Quit,
+    Help,
+    Flip,
+    Fen,
+    Pgn,
+    Moves,
+    Undo,
+    /// Anything else is move in SAN or UCI.
+    Move(String),
+}
+
+/// Parse one line of input to [Command].
+fn parse_command(line: &str) -> Command {
+    match line.trim() {
+        "" => Command::Empty,
+        "quit" | "exit" => Command::Quit,
+        "help" => Command::Help,
+        "flip" => Command::Flip,
+        "fen" => Command::Fen,
+        "pgn" => Command::Pgn,
+        "moves" => Command::Moves,
+        "undo" => Command::Undo,
+        input => Command::Move(input.to_string()),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let opponent = match args.first().map(String::as_str) {
+        None => None,
+        Some("random") => Some(Opponent::Random),
+        Some("simple") => Some(Opponent::Simple),
+        Some(other) => {
+            eprintln!("Unknown opponent {}, use random or simple.", other);
+            std::process::exit(2);
+        }
+    };
+    let computer = match args.get(1).map(String::as_str) {
+        None | Some("black") => Color::Black,
+        Some("white") => Color::White,
+        Some(other) => {
+            eprintln!("Unknown color {}, use white or black.", other);
+            std::process::exit(2);
+        }
+    };
+
+    let mut game = Game::new();
+    let mut renderer = BoardRenderer::default();
+    if opponent.is_some() {
+        game.set_tag(
+            if computer == Color::White {
+                "White"
+            } else {
+                "Black"
+            },
+            "simple_chess",
+        );
+        renderer.flipped = computer == Color::White;
+    }
+
+    println!("{}", HELP);
+    // board is rendered again only after move, undo or flip
+    let mut show = true;
+    loop {
+        let board = game.board();
+        let status = board.status();
+        if let (Some(opponent), GameStatus::Ongoing) = (opponent, status) {
+            if board.side_to_move() == computer {
+                let mv = choose_move(board, opponent);
+                println!("Computer plays {}.", mv.to_san(board).unwrap());
+                game.make_move(mv).unwrap();
+                show = true;
+                continue;
+            }
+        }
+
+        let turn = board.side_to_move();
+        let check = board.is_check(turn);
+        if show {
+            println!();
+            print!("{}", renderer.render(board));
+        }
+        match status {
+            GameStatus::Ongoing => {
+                if check && show {
+                    println!("Check!");
+                }
+                game.set_tag("Result", "*");
+            }
+            GameStatus::Checkmate(winner) => {
+                if show {
+                    println!("Checkmate, {} wins.", color_name(winner));
+                }
+                game.set_tag("Result", if winner == Color::White { "1-0" } else { "0-1" });
+            }
+            GameStatus::Stalemate => {
+                if show {
+                    println!("Stalemate, draw.");
+                }
+                game.set_tag("Result", "1/2-1/2");
+            }
+            GameStatus::Draw(reason) => {
+                if show {
+                    println!("Draw by {}.", draw_name(reason));
+                }
+                game.set_tag("Result", "1/2-1/2");
+            }
+        }
+
+        print!("{} to move> ", color_name(turn));
+        std::io::stdout().flush().unwrap();
+        show = false;
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+
+        match parse_command(&line) {
+            Command::Empty => {}
+            Command::Quit => break,
+            Command::Help => println!("{}", HELP),
+            Command::Flip => {
+                renderer.flipped = !renderer.flipped;
+                show = true;
+            }
+            Command::Fen => println!("{}", game.board().to_fen()),
+            Command::Pgn => println!("{}", game.to_pgn()),
+            Command::Moves => {
+                let board = game.board();
+                let moves: Vec<String> = board
+                    .gen_legal_moves()
+                    .into_iter()
+                    .filter_map(|mv| mv.to_san(board).ok())
+                    .collect();
+                println!("{}", moves.join(" "));
+            }
+            Command::Undo => {
+                if game.undo_move().is_none() {
+                    println!("There is no move to undo.");
+                } else if opponent.is_some() && game.board().side_to_move() == computer {
+                    // take back computer move too, so human is on the move again
+                    game.undo_move();
+                }
+                show = true;
+            }
+            Command::Move(input) if status != GameStatus::Ongoing => {
+                println!("Game is over, can't play {}.", input);
+            }
+            Command::Move(input) => {
+                match parse_move(&input, game.board()).and_then(|mv| game.make_move(mv)) {
+                    Ok(()) => show = true,
+                    Err(e) => println!("{}", e),
+                }
+            }
+        }
+    }
+}
+
+/// Parse move in SAN or UCI, UCI moves are checked later by [Game::make_move].
+fn parse_move(input: &str, board: &Board) -> Result<ChessMove, ChessError> {
+    match ChessMove::from_san(input, board) {
+        Ok(mv) => Ok(mv),
+        Err(san_error) => input.parse().map_err(|_| san_error.into()),
+    }
+}
+
+/// Choose move of side to move, there must be at least one legal move.
+fn choose_move(board: &Board, opponent: Opponent) -> ChessMove {
+    let moves = board.gen_legal_moves_info();
+    if opponent == Opponent::Random {
+        return moves[random_below(moves.len())].mv;
+    }
+
+    // random tie breaking, so computer doesn't play same game every time
+    let mut best = Vec::new();
+    let mut best_score = i32::MIN;
+    for info in moves {
+        let score = score_move(board, &info);
+        if score > best_score {
+            best_score = score;
+            best.clear();
+        }
+        if score == best_score {
+            best.push(info.mv);
+        }
+    }
+    best[random_below(best.len())]
+}
+
+/// Score move by material it wins, mate is best of all.
+fn score_move(board: &Board, info: &MoveInfo) -> i32 {
+    let mut after = board.clone();
+    after.make_move(info.mv);
+    if let GameStatus::Checkmate(_) = after.status() {
+        return i32::MAX;
+    }
+
+    let mut score = info.captured.map_or(0, |p| p.piece_type.value());
+    if let Some(promo) = info.mv.promo {
+        score += promo.value() - PieceType::Pawn.value();
+    }
+    // moved piece can be taken back
+    if !info.is_castle() && after.is_square_attacked(info.mv.dest, after.side_to_move()) {
+        score -= info.mv.promo.unwrap_or(info.piece.piece_type).value();
+    }
+    score
+}
+
+fn color_name(color: Color) -> &'static str {
+    match color {
+        Color::White => "White",
+        Color::Black => "Black",
+    }
+}
+
+fn draw_name(reason: DrawReason) -> &'static str {
+    match reason {
+        DrawReason::InsufficientMaterial => "insufficient material",
+        DrawReason::FiftyMoveRule => "fifty move rule",
+        DrawReason::ThreefoldRepetition => "threefold repetition",
+    }
+}
+
+/// Get random number in 0..n, from random seed of std hasher (same as [Book::random_move]).
+fn random_below(n: usize) -> usize {
+    (RandomState::new().hash_one(n) % n as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commands() {
+        assert_eq!(parse_command("\n"), Command::Empty);
+        assert_eq!(parse_command("quit\n"), Command::Quit);
+        assert_eq!(parse_command("exit"), Command::Quit);
+        assert_eq!(parse_command("  undo "), Command::Undo);
+        assert_eq!(parse_command("moves"), Command::Moves);
+        assert_eq!(parse_command("fen"), Command::Fen);
+        assert_eq!(parse_command("pgn"), Command::Pgn);
+        assert_eq!(parse_command("flip"), Command::Flip);
+        assert_eq!(parse_command("help"), Command::Help);
+        assert_eq!(parse_command("Nf3\n"), Command::Move("Nf3".to_string()));
+    }
+
+    #[test]
+    fn san_or_uci() {
+        let board = Board::deafult();
+        let nf3: ChessMove = "g1f3".parse().unwrap();
+        assert_eq!(parse_move("Nf3", &board), Ok(nf3));
+        assert_eq!(parse_move("g1f3", &board), Ok(nf3));
+        // UCI isn't checked here, illegal move is refused by Game::make_move
+        assert_eq!(parse_move("g1g3", &board), Ok("g1g3".parse().unwrap()));
+        assert!(Game::new().make_move("g1g3".parse().unwrap()).is_err());
+        // error of SAN is reported for input which isn't UCI either
+        assert_eq!(
+            parse_move("Nf4", &board),
+            Err(ChessError::InvalidSan(SanError::IllegalMove))
+        );
+        assert_eq!(
+            parse_move("hello", &board),
+            Err(ChessError::InvalidSan(SanError::InvalidSan))
+        );
+    }
+
+    #[test]
+    fn simple_opponent() {
+        // mate is preferred to winning queen
+        let board = Board::from_fen("6k1/5ppp/8/8/8/8/q7/3RK3 w - - 0 1").unwrap();
+        assert_eq!(
+            choose_move(&board, Opponent::Simple),
+            "d1d8".parse().unwrap()
+        );
+        // free queen is taken, defended pawn isn't
+        let board = Board::from_fen("4k3/8/8/1p6/q1p5/1P6/8/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            choose_move(&board, Opponent::Simple),
+            "b3a4".parse().unwrap()
+        );
+
+        let board = Board::deafult();
+        for _ in 0..10 {
+            let mv = choose_move(&board, Opponent::Random);
+            assert!(board.gen_legal_moves().contains(&mv));
+        }
+    }
+}
@@ -0,0 +1,488 @@
+//! Chess engine speaking UCI protocol over stdin/stdout, so library can be used from chess GUIs
+//! and tournament managers (e.g. `cutechess-cli -engine cmd=uci_engine`).
+//!
+//! Search is plain alpha-beta with quiescence search and material evaluation,
+//! it runs in its own thread, so `stop` and `quit` are handled while searching.
+
+use std::io::BufRead;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::time::Instant;
+
+use simple_chess::*;
+
+/// Score of mate in zero plies, mate in n plies is `MATE - n`.
+const MATE: i32 = 100_000;
+/// Nodes searched between checks of stop flag and time.
+const CHECK_EVERY: u64 = 1024;
+
+/// Limits of one search given by `go` command.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct Limits {
+    depth: Option<u32>,
+    /// Maximum number of searched nodes.
+    nodes: Option<u64>,
+    /// Time when search has to stop.
+    deadline: Option<Instant>,
+}
+
+fn main() {
+    let mut board = Board::deafult();
+    let mut chess960 = false;
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut search: Option<JoinHandle<()>> = None;
+
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.first().copied() {
+            Some("uci") => {
+                println!("id name simple_chess {}", env!("CARGO_PKG_VERSION"));
+                println!("id author simple_chess developers");
+                println!("option name UCI_Chess960 type check default false");
+                println!("uciok");
+            }
+            Some("isready") => println!("readyok"),
+            Some("setoption") => {
+                // setoption name UCI_Chess960 value true
+                if let [_, "name", "UCI_Chess960", "value", value] = tokens[..] {
+                    chess960 = value == "true";
+                    board.set_chess960(chess960);
+                }
+            }
+            Some("ucinewgame") => {
+                finish(&mut search, &stop);
+                board = Board::deafult();
+                board.set_chess960(chess960);
+            }
+            Some("position") => {
+                finish(&mut search, &stop);
+                if let Err(e) = parse_position(&tokens[1..], chess960, &mut board) {
+                    println!("info string {}", e);
+                }
+            }
+            Some("go") => {
+                finish(&mut search, &stop);
+                let limits = parse_go(&tokens[1..], board.side_to_move());
+                stop.store(false, Ordering::Relaxed);
+                let board = board.clone();
+                let stop = Arc::clone(&stop);
+                search = Some(std::thread::spawn(move || {
+                    let best = Search::new(stop, limits).run(board);
+                    match best {
+                        Some(mv) => println!("bestmove {}", mv),
+                        None => println!("bestmove 0000"),
+                    }
+                }));
+            }
+            Some("stop") => finish(&mut search, &stop),
+            Some("quit") => break,
+            _ => {}
+        }
+    }
+
+    finish(&mut search, &stop);
+}
+
+/// Stop running search and wait until it prints its best move.
+fn finish(search: &mut Option<JoinHandle<()>>, stop: &AtomicBool) {
+    if let Some(handle) = search.take() {
+        stop.store(true, Ordering::Relaxed);
+        handle.join().unwrap();
+    }
+}
+
+/// Set [Board] to position of `position` command: `startpos|fen <fen> [moves <uci>...]`.
+/// Previous position is never kept, invalid FEN or illegal move gives start position.
+fn parse_position(tokens: &[&str], chess960: bool, board: &mut Board) -> Result<(), ChessError> {
+    let result = apply_position(tokens, chess960, board);
+    if result.is_err() {
+        *board = Board::deafult();
+        board.set_chess960(chess960);
+    }
+    result
+}
+
+/// Apply `position` command to [Board], position is incomplete on [Err].
+fn apply_position(tokens: &[&str], chess960: bool, board: &mut Board) -> Result<(), ChessError> {
+    *board = Board::deafult();
+    board.set_chess960(chess960);
+    let moves_at = tokens
+        .iter()
+        .position(|&t| t == "moves")
+        .unwrap_or(tokens.len());
+    match tokens.first() {
+        Some(&"startpos") => {}
+        Some(&"fen") => {
+            *board = Board::from_fen(&tokens[1..moves_at].join(" "))?;
+            board.set_chess960(chess960 || board.is_chess960());
+        }
+        _ => return Err(ChessError::InvalidNotation),
+    }
+
+    for uci in tokens.iter().skip(moves_at + 1) {
+        let mv = ChessMove::from_uci(uci, board)?;
+        board.make_move(mv);
+    }
+    Ok(())
+}
+
+/// Parse arguments of `go` command to [Limits], without arguments search is infinite.
+/// `mate n` limits depth to `2n` plies, mate is recognized one ply after mating move.
+fn parse_go(tokens: &[&str], turn: Color) -> Limits {
+    let value = |name: &str| -> Option<u64> {
+        let i = tokens.iter().position(|&t| t == name)?;
+        tokens.get(i + 1)?.parse().ok()
+    };
+    let (time, inc) = match turn {
+        Color::White => (value("wtime"), value("winc")),
+        Color::Black => (value("btime"), value("binc")),
+    };
+
+    // use part of remaining time, keep some reserve for communication
+    let budget = value("movetime").or(time.map(|time| {
+        let moves_to_go = value("movestogo").unwrap_or(30).max(1);
+        (time / moves_to_go + inc.unwrap_or(0) / 2).min(time.saturating_sub(50))
+    }));
+
+    Limits {
+        depth: value("depth")
+            .or(value("mate").map(|moves| 2 * moves))
+            .map(|depth| depth as u32),
+        nodes: value("nodes"),
+        deadline: budget.map(|ms| Instant::now() + Duration::from_millis(ms.max(1))),
+    }
+}
+
+/// State of one search (iterative deepening of alpha-beta).
+struct Search {
+    stop: Arc<AtomicBool>,
+    limits: Limits,
+    start: Instant,
+    nodes: u64,
+    /// Search was interrupted, result of current iteration is incomplete.
+    aborted: bool,
+}
+
+impl Search {
+    fn new(stop: Arc<AtomicBool>, limits: Limits) -> Search {
+        Search {
+            stop,
+            limits,
+            start: Instant::now(),
+            nodes: 0,
+            aborted: false,
+        }
+    }
+
+    /// Search [Board] until limits are reached or search is stopped, return best move.
+    fn run(&mut self, mut board: Board) -> Option<ChessMove> {
+        let mut moves = ordered_moves(&board, false);
+        let mut best = moves.first().map(|info| info.mv);
+        let max_depth = self.limits.depth.unwrap_or(u32::MAX).max(1);
+
+        for depth in 1..=max_depth {
+            let mut alpha = -MATE - 1;
+            let mut best_in_depth = None;
+            for info in &moves {
+                board.make_move(info.mv);
+                let score = -self.negamax(&mut board, depth - 1, -MATE - 1, -alpha, 1);
+                board.unmake_move();
+                if self.aborted {
+                    break;
+                }
+                if score > alpha {
+                    alpha = score;
+                    best_in_depth = Some(info.mv);
+                }
+            }
+            // result of interrupted iteration is used only if its first move was searched
+            if let Some(mv) = best_in_depth {
+                best = Some(mv);
+                // best move is searched first in next iteration
+                let i = moves.iter().position(|info| info.mv == mv).unwrap();
+                moves[..=i].rotate_right(1);
+            }
+            if self.aborted || best_in_depth.is_none() {
+                break;
+            }
+
+            println!(
+                "info depth {} score {} nodes {} time {} pv {}",
+                depth,
+                uci_score(alpha),
+                self.nodes,
+                self.start.elapsed().as_millis(),
+                best.unwrap()
+            );
+            if alpha.abs() >= MATE - depth as i32 {
+                // mate was found, deeper search won't change it
+                break;
+            }
+        }
+
+        // "go infinite" and "go ponder" must not end before stop
+        if self.limits == Limits::default() {
+            while !self.stop.load(Ordering::Relaxed) {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+        }
+        best
+    }
+
+    /// Check node limit, stop flag and time from time to time.
+    fn should_stop(&mut self) -> bool {
+        self.nodes += 1;
+        if self.limits.nodes.is_some_and(|limit| self.nodes > limit) {
+            self.aborted = true;
+        }
+        if self.nodes.is_multiple_of(CHECK_EVERY)
+            && (self.stop.load(Ordering::Relaxed)
+                || self.limits.deadline.is_some_and(|d| Instant::now() >= d))
+        {
+            self.aborted = true;
+        }
+        self.aborted
+    }
+
+    /// Alpha-beta search, score is from view of side to move.
+    fn negamax(
+        &mut self,
+        board: &mut Board,
+        depth: u32,
+        mut alpha: i32,
+        beta: i32,
+        ply: i32,
+    ) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+        if board.halfmove_clock() >= 100 || board.repetition_count() >= 2 {
+            return 0;
+        }
+        if depth == 0 {
+            return self.quiescence(board, alpha, beta);
+        }
+
+        let moves = ordered_moves(board, false);
+        if moves.is_empty() {
+            return if board.is_check(board.side_to_move()) {
+                -MATE + ply
+            } else {
+                0
+            };
+        }
+
+        for info in moves {
+            board.make_move(info.mv);
+            let score = -self.negamax(board, depth - 1, -beta, -alpha, ply + 1);
+            board.unmake_move();
+            if self.aborted {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+
+    /// Search only captures and promotions, so position isn't evaluated in middle of exchange.
+    fn quiescence(&mut self, board: &mut Board, mut alpha: i32, beta: i32) -> i32 {
+        if self.should_stop() {
+            return 0;
+        }
+        let stand_pat = evaluate(board);
+        if stand_pat >= beta {
+            return beta;
+        }
+        alpha = alpha.max(stand_pat);
+
+        for info in ordered_moves(board, true) {
+            board.make_move(info.mv);
+            let score = -self.quiescence(board, -beta, -alpha);
+            board.unmake_move();
+            if self.aborted {
+                return 0;
+            }
+            if score >= beta {
+                return beta;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+}
+
+/// Legal moves of side to move, captures of valuable pieces by cheap pieces first.
+fn ordered_moves(board: &Board, only_tactical: bool) -> Vec<MoveInfo> {
+    let mut moves: Vec<MoveInfo> = board
+        .gen_legal_moves_info()
+        .into_iter()
+        .filter(|info| !only_tactical || info.is_capture() || info.mv.promo.is_some())
+        .collect();
+    moves.sort_by_key(|info| {
+        let gain = info.captured.map_or(0, |p| 10 * p.piece_type.value())
+            - info.piece.piece_type.value()
+            + info.mv.promo.map_or(0, PieceType::value);
+        std::cmp::Reverse(gain)
+    });
+    moves
+}
+
+/// Evaluate position from view of side to move: material and small bonus for central pieces
+/// and advanced pawns.
+fn evaluate(board: &Board) -> i32 {
+    let mut score = 0;
+    for sq in board.occupied() {
+        let piece = board.get(sq).unwrap();
+        let (row, col) = ((sq.0 / 8) as i32, (sq.0 % 8) as i32);
+        let center = 6 - (2 * row - 7).abs() / 2 - (2 * col - 7).abs() / 2;
+        let bonus = match piece.piece_type {
+            PieceType::Pawn => match piece.color {
+                Color::White => (6 - row) * 5,
+                Color::Black => (row - 1) * 5,
+            },
+            PieceType::Knight | PieceType::Bishop => center * 5,
+            _ => 0,
+        };
+        let piece_score = piece.piece_type.value() + bonus;
+        if piece.color == board.side_to_move() {
+            score += piece_score;
+        } else {
+            score -= piece_score;
+        }
+    }
+    score
+}
+
+/// Format score for `info` output, mate scores are in moves (not plies).
+fn uci_score(score: i32) -> String {
+    if score >= MATE - 1000 {
+        format!("mate {}", (MATE - score + 1) / 2)
+    } else if score <= -MATE + 1000 {
+        format!("mate -{}", (MATE + score) / 2)
+    } else {
+        format!("cp {}", score)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Run `position` command (without "position" token), return result and FEN of position.
+    fn position(command: &str) -> (Result<(), ChessError>, String) {
+        let tokens: Vec<&str> = command.split_whitespace().collect();
+        let mut board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let result = parse_position(&tokens, false, &mut board);
+        (result, board.to_fen())
+    }
+
+    #[test]
+    fn position_command() {
+        assert_eq!(position("startpos"), (Ok(()), STARTING_FEN.to_string()));
+        assert_eq!(
+            position("startpos moves e2e4 e7e5 g1f3"),
+            (
+                Ok(()),
+                "rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2".to_string()
+            )
+        );
+        assert_eq!(
+            position("fen 4k3/8/8/8/8/8/8/R3K3 w Q - 0 1 moves e1c1 e8f7"),
+            (Ok(()), "8/5k2/8/8/8/8/8/2KR4 w - - 2 2".to_string())
+        );
+
+        // invalid command gives start position, not previous one
+        let (result, fen) = position("startpos moves e2e4 e2e4");
+        assert_eq!(result, Err(ChessError::InvalidUci(UciError::IllegalMove)));
+        assert_eq!(fen, STARTING_FEN);
+        let (result, fen) = position("fen 4k3/8/8 w - - 0 1 moves e1d1");
+        assert!(matches!(result, Err(ChessError::InvalidFen(_))));
+        assert_eq!(fen, STARTING_FEN);
+        let (result, fen) = position("startpos moves e2e9");
+        assert_eq!(result, Err(ChessError::InvalidUci(UciError::InvalidUci)));
+        assert_eq!(fen, STARTING_FEN);
+        assert_eq!(
+            position("somewhere"),
+            (Err(ChessError::InvalidNotation), STARTING_FEN.to_string())
+        );
+
+        // Chess960 castle is king takes own rook
+        let tokens = [
+            "startpos", "moves", "e2e4", "e7e5", "g1f3", "b8c6", "f1c4", "g8f6", "e1h1",
+        ];
+        let mut board = Board::deafult();
+        assert_eq!(parse_position(&tokens, true, &mut board), Ok(()));
+        assert!(board.is_chess960());
+        assert_eq!(
+            *board.get(Square::G1),
+            Some(Piece::new(PieceType::King, Color::White))
+        );
+    }
+
+    #[test]
+    fn go_command() {
+        let go = |command: &str, turn| {
+            let tokens: Vec<&str> = command.split_whitespace().collect();
+            parse_go(&tokens, turn)
+        };
+        assert_eq!(go("", Color::White), Limits::default());
+        assert_eq!(go("infinite", Color::White), Limits::default());
+        assert_eq!(go("depth 5", Color::White).depth, Some(5));
+        assert_eq!(go("mate 3", Color::White).depth, Some(6));
+        assert_eq!(go("nodes 5000", Color::Black).nodes, Some(5000));
+
+        let remaining = |limits: Limits| {
+            limits
+                .deadline
+                .unwrap()
+                .saturating_duration_since(Instant::now())
+                .as_millis()
+        };
+        let ms = remaining(go("movetime 1000", Color::White));
+        assert!((900..=1000).contains(&ms), "{}", ms);
+        // 30 moves to go by deafult, time of side to move is used
+        let command = "wtime 60000 btime 3000 winc 0 binc 0";
+        let ms = remaining(go(command, Color::White));
+        assert!((1900..=2000).contains(&ms), "{}", ms);
+        let ms = remaining(go(command, Color::Black));
+        assert!((0..=100).contains(&ms), "{}", ms);
+        let ms = remaining(go("wtime 10000 movestogo 2 winc 1000", Color::White));
+        assert!((5400..=5500).contains(&ms), "{}", ms);
+    }
+
+    #[test]
+    fn bounded_search() {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let limits = parse_go(&["nodes", "2000"], Color::White);
+        let mut search = Search::new(Arc::clone(&stop), limits);
+        assert!(search.run(Board::deafult()).is_some());
+        assert!(search.nodes <= 2001);
+
+        // Qg7 is mate
+        let mut board = Board::from_fen("7k/8/5K2/8/8/8/8/6Q1 w - - 0 1").unwrap();
+        let limits = parse_go(&["mate", "1"], Color::White);
+        let best = Search::new(stop, limits).run(board.clone()).unwrap();
+        board.make_move(best);
+        assert_eq!(board.status(), GameStatus::Checkmate(Color::White));
+    }
+
+    #[test]
+    fn score_format() {
+        assert_eq!(uci_score(0), "cp 0");
+        assert_eq!(uci_score(-135), "cp -135");
+        assert_eq!(uci_score(MATE - 1), "mate 1");
+        assert_eq!(uci_score(MATE - 3), "mate 2");
+        assert_eq!(uci_score(-MATE + 2), "mate -1");
+        assert_eq!(uci_score(-MATE + 4), "mate -2");
+    }
+}
@@ -0,0 +1,185 @@
+//! [BitBoard], set of squares stored in single [u64].
+
+use crate::Color;
+use crate::PieceType;
+use crate::Square;
+
+/// Set of [Square]s, bit n is set when Square(n) is in the set.
+/// Supports set operations via bit operators (`&`, `|`, `^`, `!`) and iteration over [Square]s.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BitBoard(pub u64);
+
+impl BitBoard {
+    /// Empty set.
+    pub const EMPTY: BitBoard = BitBoard(0);
+    /// Set of all squares.
+    pub const FULL: BitBoard = BitBoard(u64::MAX);
+    /// All squares of file A.
+    pub const FILE_A: BitBoard = BitBoard(0x0101_0101_0101_0101);
+    /// All squares of file H.
+    pub const FILE_H: BitBoard = BitBoard(0x8080_8080_8080_8080);
+    /// All squares of eighth rank.
+    pub const RANK_8: BitBoard = BitBoard(0xff);
+    /// All squares of first rank.
+    pub const RANK_1: BitBoard = BitBoard(0xff << 56);
+
+    /// Create [BitBoard] with single [Square].
+    pub fn from_square(sq: Square) -> BitBoard {
+        BitBoard(1 << sq.0)
+    }
+
+    /// Check if [Square] is in the set.
+    pub fn contains(self, sq: Square) -> bool {
+        self.0 & (1 << sq.0) != 0
+    }
+
+    /// Check if set is empty.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Get number of squares in the set.
+    pub fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Get [Square] with lowest index (A8 is lowest, H1 is highest).
+    pub fn first(self) -> Option<Square> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(Square(self.0.trailing_zeros() as usize))
+        }
+    }
+}
+
+impl BitBoard {
+    /// Iterate over [Square]s of the set, from lowest index.
+    pub fn iter(self) -> BitBoardIter {
+        BitBoardIter(self.0)
+    }
+}
+
+impl IntoIterator for BitBoard {
+    type Item = Square;
+    type IntoIter = BitBoardIter;
+
+    fn into_iter(self) -> BitBoardIter {
+        self.iter()
+    }
+}
+
+/// Iterator over [Square]s of [BitBoard], created by [BitBoard::iter].
+#[derive(Clone, Debug)]
+pub struct BitBoardIter(u64);
+
+impl Iterator for BitBoardIter {
+    type Item = Square;
+
+    /// Remove and return [Square] with lowest index.
+    fn next(&mut self) -> Option<Square> {
+        let sq = BitBoard(self.0).first()?;
+        self.0 &= self.0 - 1;
+        Some(sq)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for BitBoardIter {}
+
+impl std::ops::BitAnd for BitBoard {
+    type Output = BitBoard;
+
+    fn bitand(self, rhs: BitBoard) -> BitBoard {
+        BitBoard(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitOr for BitBoard {
+    type Output = BitBoard;
+
+    fn bitor(self, rhs: BitBoard) -> BitBoard {
+        BitBoard(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitXor for BitBoard {
+    type Output = BitBoard;
+
+    fn bitxor(self, rhs: BitBoard) -> BitBoard {
+        BitBoard(self.0 ^ rhs.0)
+    }
+}
+
+impl std::ops::Not for BitBoard {
+    type Output = BitBoard;
+
+    fn not(self) -> BitBoard {
+        BitBoard(!self.0)
+    }
+}
+
+impl std::ops::BitAndAssign for BitBoard {
+    fn bitand_assign(&mut self, rhs: BitBoard) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl std::ops::BitOrAssign for BitBoard {
+    fn bitor_assign(&mut self, rhs: BitBoard) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl std::ops::BitXorAssign for BitBoard {
+    fn bitxor_assign(&mut self, rhs: BitBoard) {
+        self.0 ^= rhs.0;
+    }
+}
+
+/// Index of [PieceType] in [crate::Board] bitboards.
+pub(crate) fn piece_index(piece_type: PieceType) -> usize {
+    match piece_type {
+        PieceType::Pawn => 0,
+        PieceType::Knight => 1,
+        PieceType::Bishop => 2,
+        PieceType::Rook => 3,
+        PieceType::Queen => 4,
+        PieceType::King => 5,
+    }
+}
+
+/// Index of [Color] in [crate::Board] bitboards.
+pub(crate) fn color_index(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterate() {
+        let bb = BitBoard::from_square(Square::H1) | BitBoard::from_square(Square::A8);
+        let squares: Vec<Square> = bb.iter().collect();
+        assert_eq!(squares, vec![Square::A8, Square::H1]);
+        let mut found = Vec::new();
+        for sq in bb {
+            found.push(sq);
+        }
+        assert_eq!(found, squares);
+        // iteration doesn't change the set itself
+        assert_eq!(bb.count(), 2);
+        assert_eq!(bb.first(), Some(Square::A8));
+        assert_eq!(bb.iter().len(), 2);
+        assert_eq!(BitBoard::EMPTY.iter().next(), None);
+        assert_eq!(BitBoard::FULL.iter().len(), 64);
+    }
+}
@@ -1,3 +1,9 @@
+use crate::bitboard::color_index;
+use crate::bitboard::piece_index;
+use crate::zobrist::piece_key;
+use crate::zobrist::TURN_KEY;
+use crate::BitBoard;
+use crate::ChessError;
 use crate::ChessMove;
 use crate::Color;
 use crate::File;
@@ -6,98 +12,228 @@ use crate::PieceType;
 use crate::Rank;
 use crate::Square;
 
+/// State of [Board] before move, which can't be computed back from position.
+/// Saved by [Board::make_move] and restored by [Board::unmake_move].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct BoardState {
+    mv: ChessMove,
+    pieces: [BitBoard; 6],
+    colors: [BitBoard; 2],
+    hash: u64,
+    castling: [[Option<File>; 2]; 2],
+    turn: Color,
+    en_passant: Option<Square>,
+    halfmove_clock: u32,
+    fullmove_number: u32,
+}
+
+/// Index of kingside castling right in [Board::castling_rights].
+pub const KINGSIDE: usize = 0;
+/// Index of queenside castling right in [Board::castling_rights].
+pub const QUEENSIDE: usize = 1;
+
 /// [Board] stores position and history of position.
-/// Position is represent by array of [Option<Piece>], with unchangeable size of 8x8.
+/// Position is represent by array of [Option<Piece>], with unchangeable size of 8x8,
+/// and by [BitBoard]s of every [PieceType] and [Color] (used for move generation).
 #[derive(Clone, Debug, PartialEq)]
 pub struct Board {
     pos: [[Option<Piece>; 8]; 8],
-    pub(crate) king_moved_w: bool,
-    pub(crate) left_rook_moved_w: bool,
-    pub(crate) right_rook_moved_w: bool,
-    pub(crate) king_moved_b: bool,
-    pub(crate) left_rook_moved_b: bool,
-    pub(crate) right_rook_moved_b: bool,
+    /// Squares of pieces by [PieceType] (both colors).
+    pub(crate) pieces: [BitBoard; 6],
+    /// Squares of pieces by [Color].
+    pub(crate) colors: [BitBoard; 2],
+    /// Zobrist hash of position, see [Board::hash].
+    pub(crate) hash: u64,
+    /// Castling rights as [File] of castling rook, indexed by [Color] and side
+    /// ([KINGSIDE] or [QUEENSIDE]). Right is lost when king or that rook moves.
+    pub(crate) castling: [[Option<File>; 2]; 2],
+    /// Castles are encoded as king takes own rook (Chess960), not as king moving two squares.
+    pub(crate) chess960: bool,
     /// Record all moves. Doesn't contains current position(Current is in board.pos).
     history: Vec<[[Option<Piece>; 8]; 8]>,
-    /// Sets new [PieceType] of promoted pawn.
-    pub pawn_promo: PieceType,
+    /// Record [Board::hash] of all previous positions, used for repetition detection.
+    pub(crate) key_history: Vec<u64>,
+    /// Record [BoardState] before every move, used for undoing moves.
+    state_history: Vec<BoardState>,
+    /// [Color] which is on the move.
+    pub(crate) turn: Color,
+    /// Square skipped by pawn double push in last move (target for en passant).
+    pub(crate) en_passant: Option<Square>,
+    /// Number of halfmoves since last capture or pawn move.
+    pub(crate) halfmove_clock: u32,
+    /// Number of full move, starts at 1 and grows after black move.
+    pub(crate) fullmove_number: u32,
+}
+
+/// Mutable rank of [Board] returned by [Board::get_mut_rank], dereferences to
+/// `[Option<Piece>; 8]`. Changes are written to [BitBoard]s and hash of [Board] on drop.
+#[derive(Debug)]
+pub struct RankMut<'a> {
+    board: &'a mut Board,
+    row: usize,
+}
+
+impl std::ops::Deref for RankMut<'_> {
+    type Target = [Option<Piece>; 8];
+
+    fn deref(&self) -> &Self::Target {
+        &self.board.pos[self.row]
+    }
+}
+
+impl std::ops::DerefMut for RankMut<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.board.pos[self.row]
+    }
+}
+
+impl Drop for RankMut<'_> {
+    fn drop(&mut self) {
+        self.board.sync_pieces();
+    }
 }
 
 impl Board {
     /// Setup [Board] position to deaufult chess position.
-    /// Deafult pawn_promo is set to Queen.
     pub fn deafult() -> Board {
         let army = [
             PieceType::Rook,
             PieceType::Knight,
             PieceType::Bishop,
-            PieceType::King,
             PieceType::Queen,
+            PieceType::King,
             PieceType::Bishop,
             PieceType::Knight,
             PieceType::Rook,
         ];
+        Board::from_army(army)
+    }
 
+    /// Setup [Board] with pawns and `army` on back ranks, castling with outermost rooks.
+    pub(crate) fn from_army(army: [PieceType; 8]) -> Board {
         let mut board = Board::empty(Color::White);
 
-        for i in 0..8 {
-            *board.get_mut(Square::new(
-                Rank::try_from_usize(0).unwrap(),
-                File::try_from_usize(i).unwrap(),
-            )) = Some(Piece {
-                piece_type: army[i],
-                color: Color::Black,
-            });
-
-            *board.get_mut(Square::new(
-                Rank::try_from_usize(7).unwrap(),
-                File::try_from_usize(i).unwrap(),
-            )) = Some(Piece {
-                piece_type: army[i],
-                color: Color::White,
-            });
+        for (i, piece_type) in army.into_iter().enumerate() {
+            let file = File::try_from_usize(i).unwrap();
+            board.place_piece(
+                Square::new(Rank::Eighth, file),
+                Some(Piece::new(piece_type, Color::Black)),
+            );
+            board.place_piece(
+                Square::new(Rank::Seventh, file),
+                Some(Piece::new(PieceType::Pawn, Color::Black)),
+            );
+            board.place_piece(
+                Square::new(Rank::Second, file),
+                Some(Piece::new(PieceType::Pawn, Color::White)),
+            );
+            board.place_piece(
+                Square::new(Rank::First, file),
+                Some(Piece::new(piece_type, Color::White)),
+            );
         }
 
-        board.pos[1] = [Some(Piece {
-            piece_type: PieceType::Pawn,
-            color: Color::Black,
-        }); 8];
-
-        board.pos[6] = [Some(Piece {
-            piece_type: PieceType::Pawn,
-            color: Color::White,
-        }); 8];
+        // rooks on the side of king
+        let king = army.iter().position(|&p| p == PieceType::King).unwrap();
+        let mut rooks = (0..8).filter(|&f| army[f] == PieceType::Rook);
+        let kingside = rooks.clone().rfind(|&f| f > king);
+        let queenside = rooks.find(|&f| f < king);
+        board.castling =
+            [[kingside, queenside].map(|f| f.and_then(|f| File::try_from_usize(f).ok())); 2];
+        board.hash = board.compute_hash();
 
         board
     }
 
-    /// Return empty [Board] use for building chess position.
-    /// Deafult pawn_promo is set to Queen.
+    /// Return empty [Board] use for building chess position, `turn` is side to move.
+    /// Board has no castling rights (set them by FEN or use [Board::deafult]).
     pub fn empty(turn: Color) -> Self {
         let pos = [[None; 8]; 8];
-        Board {
+        let mut board = Board {
             pos,
+            pieces: [BitBoard::EMPTY; 6],
+            colors: [BitBoard::EMPTY; 2],
+            hash: 0,
             history: Vec::new(),
-            pawn_promo: PieceType::Queen,
-            king_moved_w: false,
-            left_rook_moved_w: false,
-            right_rook_moved_w: false,
-            king_moved_b: false,
-            left_rook_moved_b: false,
-            right_rook_moved_b: false,
-        }
+            key_history: Vec::new(),
+            state_history: Vec::new(),
+            castling: [[None; 2]; 2],
+            chess960: false,
+            turn,
+            en_passant: None,
+            halfmove_clock: 0,
+            fullmove_number: 1,
+        };
+        board.hash = board.compute_hash();
+        board
     }
 
     /// Place(or replace) piece on board(doesn't included in history).
+    /// Castling rights, which lost their king or rook on back rank, are removed.
     pub fn place_piece(&mut self, index: Square, piece: Option<Piece>) -> &mut Board {
-        *self.get_mut(index) = piece;
+        self.set_square(index, piece);
+        self.drop_unbacked_castling();
         self
     }
 
+    /// Change piece on square, this is the only place where position is changed,
+    /// it keeps [BitBoard]s and hash in sync (castling rights aren't checked).
+    fn set_square(&mut self, index: Square, piece: Option<Piece>) {
+        let bit = BitBoard::from_square(index);
+        if let Some(old) = *self.get(index) {
+            self.pieces[piece_index(old.piece_type)] &= !bit;
+            self.colors[color_index(old.color)] &= !bit;
+            self.hash ^= piece_key(old, index);
+        }
+        if let Some(new) = piece {
+            self.pieces[piece_index(new.piece_type)] |= bit;
+            self.colors[color_index(new.color)] |= bit;
+            self.hash ^= piece_key(new, index);
+        }
+        *self.get_mut(index) = piece;
+    }
+
+    /// Remove castling rights without king and rook on back rank (rook on the side of right).
+    fn drop_unbacked_castling(&mut self) {
+        self.hash ^= self.castling_hash();
+        for color in [Color::White, Color::Black] {
+            let row = back_rank(color);
+            let own = self.colors[color_index(color)];
+            let rooks = self.pieces[piece_index(PieceType::Rook)] & own;
+            let king = (self.pieces[piece_index(PieceType::King)] & own)
+                .iter()
+                .find(|sq| sq.0 / 8 == row);
+            for (side, right) in self.castling[color_index(color)].iter_mut().enumerate() {
+                let backed = right.zip(king).is_some_and(|(file, king)| {
+                    let rook = Square(row * 8 + file.to_usize());
+                    rooks.contains(rook) && (rook.0 > king.0) == (side == KINGSIDE)
+                });
+                if !backed {
+                    *right = None;
+                }
+            }
+        }
+        self.hash ^= self.castling_hash();
+    }
+
     /// Remove piece on board(doesn't included in history).
     pub fn remove_piece(&mut self, index: Square) -> &mut Board {
-        *self.get_mut(index) = None;
-        self
+        self.place_piece(index, None)
+    }
+
+    /// Get [BitBoard] of all pieces of [PieceType] (both colors).
+    pub fn pieces(&self, piece_type: PieceType) -> BitBoard {
+        self.pieces[piece_index(piece_type)]
+    }
+
+    /// Get [BitBoard] of all pieces of [Color].
+    pub fn color_pieces(&self, color: Color) -> BitBoard {
+        self.colors[color_index(color)]
+    }
+
+    /// Get [BitBoard] of all occupied squares.
+    pub fn occupied(&self) -> BitBoard {
+        self.colors[0] | self.colors[1]
     }
 
     /// Get [Option<Square>] from reference on piece.
@@ -117,8 +253,17 @@ impl Board {
     }
 
     /// Get reference to specific [Option<Piece>] on the [Board] from last history record.
+    /// Return reference to [None] if history is empty.
     pub fn get_from_previous(&self, index: Square) -> &Option<Piece> {
-        &self.history[self.history.len() - 1][index.0 / 8][index.0 % 8]
+        match self.history.last() {
+            Some(pos) => &pos[index.0 / 8][index.0 % 8],
+            None => &None,
+        }
+    }
+
+    /// Get en passant target square (square skipped by pawn double push in last move).
+    pub fn en_passant(&self) -> Option<Square> {
+        self.en_passant
     }
 
     /// Get mutable reference to specific [Option<Piece>] on the [Board].
@@ -127,8 +272,35 @@ impl Board {
     }
 
     /// Get reference to specific rank on the [Board].
-    pub fn get_mut_rank(&mut self, rank: Rank) -> &mut [Option<Piece>; 8] {
-        &mut self.pos[rank.to_usize()]
+    /// Use [Board::place_piece] for changing position.
+    pub fn get_rank(&self, rank: Rank) -> &[Option<Piece>; 8] {
+        &self.pos[rank.to_usize()]
+    }
+
+    /// Get mutable reference to specific rank on the [Board].
+    /// [BitBoard]s and hash are updated from the rank when returned [RankMut] is dropped,
+    /// prefer [Board::place_piece] for changing single square.
+    pub fn get_mut_rank(&mut self, rank: Rank) -> RankMut<'_> {
+        RankMut {
+            row: rank.to_usize(),
+            board: self,
+        }
+    }
+
+    /// Rebuild [BitBoard]s, castling rights and hash from position changed directly
+    /// (not by [Board::place_piece]).
+    fn sync_pieces(&mut self) {
+        self.pieces = [BitBoard::EMPTY; 6];
+        self.colors = [BitBoard::EMPTY; 2];
+        for i in 0..64 {
+            if let Some(piece) = *self.get(Square(i)) {
+                let bit = BitBoard::from_square(Square(i));
+                self.pieces[piece_index(piece.piece_type)] |= bit;
+                self.colors[color_index(piece.color)] |= bit;
+            }
+        }
+        self.drop_unbacked_castling();
+        self.hash = self.compute_hash();
     }
 
     /// Get refernce to history.
@@ -162,41 +334,82 @@ impl Board {
     //     all.into_iter()
     // }
 
-    /// Clear history except last record if is there beacause, en passant move.
-    /// Draw rules maybe got a little bit broken. 
-    pub(crate) fn clear_history(&mut self) {
-        self.history.clear();
+    /// Get number of halfmoves since last capture or pawn move.
+    pub fn halfmove_clock(&self) -> u32 {
+        self.halfmove_clock
+    }
+
+    /// Get number of full move, starts at 1 and grows after black move.
+    pub fn fullmove_number(&self) -> u32 {
+        self.fullmove_number
+    }
+
+    /// Get last made [ChessMove], [None] if no move was made.
+    pub fn last_move(&self) -> Option<ChessMove> {
+        self.state_history.last().map(|state| state.mv)
+    }
+
+    /// Undo last move and restore exact previous state of the [Board].
+    /// Return undone [ChessMove] or [None] if there is no move to undo.
+    pub fn unmake_move(&mut self) -> Option<ChessMove> {
+        let state = self.state_history.pop()?;
+        self.pos = self.history.pop()?;
+        self.pieces = state.pieces;
+        self.colors = state.colors;
+        self.hash = state.hash;
+        self.key_history.pop();
+
+        self.castling = state.castling;
+        self.turn = state.turn;
+        self.en_passant = state.en_passant;
+        self.halfmove_clock = state.halfmove_clock;
+        self.fullmove_number = state.fullmove_number;
+
+        Some(state.mv)
     }
 
-    /// Make move and save new position to history.
+    /// Undo last n moves, stops when there is no move to undo.
+    /// Return number of undone moves.
+    pub fn undo_n(&mut self, n: usize) -> usize {
+        let mut undone = 0;
+        while undone < n && self.unmake_move().is_some() {
+            undone += 1;
+        }
+        undone
+    }
+
+    /// Make move and save new position to history, then pass turn to the other side.
     /// Also handling exceptions like en passant, castle and apwn promotinon.
+    /// Move isn't checked, use [Board::try_make_move] for moves from untrusted source.
     pub fn make_move(&mut self, mv: ChessMove) {
         self.history.push(self.pos);
+        self.key_history.push(self.hash);
+        self.state_history.push(BoardState {
+            mv,
+            pieces: self.pieces,
+            colors: self.colors,
+            hash: self.hash,
+            castling: self.castling,
+            turn: self.turn,
+            en_passant: self.en_passant,
+            halfmove_clock: self.halfmove_clock,
+            fullmove_number: self.fullmove_number,
+        });
+
+        // castling rights and en passant are hashed again after move
+        self.hash ^= self.castling_hash() ^ self.en_passant_hash();
 
         let mut maybe_piece = *self.get(mv.start);
-        let mut en_passant = false;
-        let mut castle_short_w = false;
-        let mut castle_short_b = false;
-        let mut castle_long_w = false;
-        let mut castle_long_b = false;
+        let castle_rook = self.castle_rook(mv);
+        // castling king "takes" own rook in Chess960 encoding
+        let capture = self.get(mv.dest).is_some() && castle_rook.is_none();
+        let mut pawn_move = false;
+        let mut double_push = false;
+        let en_passant = self.en_passant_victim(mv);
         if let Some(piece) = maybe_piece {
-            if mv.start.get_rank().unwrap()
-                == if piece.color == Color::White {
-                    Rank::Fifth
-                } else {
-                    Rank::Fourth
-                }
-                && mv.dest.get_rank().unwrap()
-                    == if piece.color == Color::White {
-                        Rank::Sixth
-                    } else {
-                        Rank::Third
-                    }
-                && *self.get(mv.dest) == None
-                && piece.piece_type == PieceType::Pawn
-            {
-                // en passant
-                en_passant = true;
+            if piece.piece_type == PieceType::Pawn {
+                pawn_move = true;
+                double_push = mv.start.0.abs_diff(mv.dest.0) == 16;
             }
 
             if let Some(promo) = mv.promo {
@@ -205,98 +418,334 @@ impl Board {
 
             match piece.piece_type {
                 PieceType::Rook => {
-                    // long castle
-                    // lw
-                    if !self.left_rook_moved_w && mv.start == Square::A1 {
-                        self.left_rook_moved_w = true;
-                    }
-                    // lb
-                    if !self.right_rook_moved_b && mv.start == Square::H8 {
-                        self.right_rook_moved_b = true;
-                    }
-                    // short castle
-                    // rw
-                    if !self.right_rook_moved_w && mv.start == Square::H1 {
-                        self.right_rook_moved_w = true;
-                    }
-                    // rb
-                    if !self.left_rook_moved_b && mv.start == Square::A8 {
-                        self.left_rook_moved_b = true;
-                    }
+                    // rook leaving its home square loses castling right
+                    self.rook_left_home(mv.start);
                 }
                 PieceType::King => {
-                    // w castle logic for moving rook
-                    if !self.king_moved_w {
-                        if mv.dest == Square::G1 {
-                            castle_short_w = true;
-                        } else if mv.dest == Square::C1 {
-                            castle_long_w = true;
-                        }
-                    }
-                    // w castle logic for moving rook
-                    if !self.king_moved_b {
-                        if mv.dest == Square::G8 {
-                            castle_short_b = true;
-                        } else if mv.dest == Square::C8 {
-                            castle_long_b = true;
-                        }
-                    }
-
-                    if piece.color == Color::White {
-                        self.king_moved_w = true;
-                    } else {
-                        self.king_moved_b = true;
-                    }
+                    self.castling[color_index(piece.color)] = [None, None];
                 }
                 _ => {}
             }
         }
 
-        self.place_piece(mv.dest, maybe_piece);
-        self.remove_piece(mv.start);
+        // rook captured on its home square can't castle anymore
+        if capture {
+            self.rook_left_home(mv.dest);
+        }
+
+        if let (Some(rook_sq), Some(king)) = (castle_rook, maybe_piece) {
+            // king and rook can swap or land on each other's squares, so remove both first
+            let (king_dest, rook_dest) = castle_dests(king.color, rook_sq.0 > mv.start.0);
+            let rook = *self.get(rook_sq);
+            self.set_square(mv.start, None);
+            self.set_square(rook_sq, None);
+            self.set_square(king_dest, Some(king));
+            self.set_square(rook_dest, rook);
+        } else {
+            self.set_square(mv.dest, maybe_piece);
+            self.set_square(mv.start, None);
+        }
         // en passant
-        if en_passant {
-            self.place_piece(
-                ChessMove::down(&self, self.get(mv.dest), 1, None)
-                    .unwrap()
-                    .dest,
-                None,
-            );
+        if let Some(victim) = en_passant {
+            self.set_square(victim, None);
+        }
+
+        // en passant square for next move
+        self.en_passant = if double_push {
+            Some(Square((mv.start.0 + mv.dest.0) / 2))
+        } else {
+            None
+        };
+
+        // clocks, fifty move rule counts from last capture or pawn move
+        if capture || pawn_move {
+            self.halfmove_clock = 0;
+        } else {
+            self.halfmove_clock += 1;
+        }
+
+        // side to move
+        if self.turn == Color::Black {
+            self.fullmove_number += 1;
+        }
+        self.turn = self.turn.get_inverse();
+
+        self.hash ^= TURN_KEY ^ self.castling_hash() ^ self.en_passant_hash();
+    }
+
+    /// Remove castling right of rook on [Square], if it is home square of castling rook.
+    fn rook_left_home(&mut self, sq: Square) {
+        for color in [Color::White, Color::Black] {
+            if sq.0 / 8 != back_rank(color) {
+                continue;
+            }
+            for right in self.castling[color_index(color)].iter_mut() {
+                if right.map(File::to_usize) == Some(sq.0 % 8) {
+                    *right = None;
+                }
+            }
         }
-        // castle
-        if castle_short_w {
-            self.place_piece(Square::F1, *self.get(Square::H1));
-            self.remove_piece(Square::H1);
+    }
+
+    /// Get square of rook which castles with [ChessMove], [None] if move isn't castle.
+    /// King taking own rook is castle, in standard chess also king moving two squares.
+    pub(crate) fn castle_rook(&self, mv: ChessMove) -> Option<Square> {
+        let king = (*self.get(mv.start))?;
+        if king.piece_type != PieceType::King {
+            return None;
         }
-        if castle_long_w {
-            self.place_piece(Square::D1, *self.get(Square::A1));
-            self.remove_piece(Square::A1);
+        if *self.get(mv.dest) == Some(Piece::new(PieceType::Rook, king.color)) {
+            return Some(mv.dest);
         }
-        if castle_short_b {
-            self.place_piece(Square::F8, *self.get(Square::H8));
-            self.remove_piece(Square::H8);
+        if self.chess960 || mv.start.0 / 8 != mv.dest.0 / 8 || mv.start.0.abs_diff(mv.dest.0) != 2 {
+            return None;
         }
-        if castle_long_b {
-            self.place_piece(Square::D8, *self.get(Square::A8));
-            self.remove_piece(Square::A8);
+        let side = if mv.dest.0 > mv.start.0 {
+            KINGSIDE
+        } else {
+            QUEENSIDE
+        };
+        let file = self.castling[color_index(king.color)][side]?;
+        Some(Square(mv.start.0 - mv.start.0 % 8 + file.to_usize()))
+    }
+
+    /// Get square of pawn taken en passant by [ChessMove], [None] if move isn't en passant.
+    /// Pawn has to move diagonally to en passant square and enemy pawn has to stand
+    /// on start rank and dest file.
+    pub(crate) fn en_passant_victim(&self, mv: ChessMove) -> Option<Square> {
+        let pawn = (*self.get(mv.start))?;
+        if pawn.piece_type != PieceType::Pawn
+            || Some(mv.dest) != self.en_passant
+            || mv.start.0 % 8 == mv.dest.0 % 8
+        {
+            return None;
         }
+        let victim = Square(mv.start.0 - mv.start.0 % 8 + mv.dest.0 % 8);
+        (*self.get(victim) == Some(Piece::new(PieceType::Pawn, pawn.color.get_inverse())))
+            .then_some(victim)
+    }
+
+    /// Get castling rights of [Color] as [File]s of castling rooks,
+    /// indexed by side ([KINGSIDE] or [QUEENSIDE]).
+    pub fn castling_rights(&self, color: Color) -> [Option<File>; 2] {
+        self.castling[color_index(color)]
+    }
+
+    /// Check if [Board] is Chess960 position (castles are encoded as king takes own rook).
+    pub fn is_chess960(&self) -> bool {
+        self.chess960
+    }
+
+    /// Switch Chess960 encoding of castles, see [Board::is_chess960].
+    pub fn set_chess960(&mut self, chess960: bool) -> &mut Board {
+        self.chess960 = chess960;
+        self
+    }
+
+    /// Make move only if it is legal move of side to move, return [Err] otherwise.
+    #[deprecated(note = "use Board::try_make_move")]
+    pub fn make_move_checked(&mut self, mv: ChessMove) -> Result<(), ChessError> {
+        self.try_make_move(mv)?;
+        Ok(())
+    }
+
+    /// Return [Color] which is on the move.
+    pub fn side_to_move(&self) -> Color {
+        self.turn
     }
 
     /*
     pub fn is_valid(turn: Color) -> bool {
         todo!()
     }
+    */
+}
 
-    pub(crate) fn is_check() -> bool {
-        todo!()
+/// Get row of back rank of [Color] (rank 1 for white, rank 8 for black).
+pub(crate) fn back_rank(color: Color) -> usize {
+    match color {
+        Color::White => 7,
+        Color::Black => 0,
     }
+}
 
-    pub(crate) fn is_check_mate() -> bool {
-        todo!()
+/// Get destination squares of king and rook after castle, both are same as in standard chess.
+pub(crate) fn castle_dests(color: Color, kingside: bool) -> (Square, Square) {
+    let row = back_rank(color) * 8;
+    if kingside {
+        (Square(row + 6), Square(row + 5))
+    } else {
+        (Square(row + 2), Square(row + 3))
     }
+}
 
-    pub(crate) fn is_draw() -> bool {
-        todo!()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mut_rank() {
+        let mut board = Board::deafult();
+        board.get_mut_rank(Rank::Second)[4] = None;
+        let mut rank = board.get_mut_rank(Rank::First);
+        rank[6] = None;
+        rank[5] = rank[3].take();
+        drop(rank);
+
+        let expected =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPP1PPP/RNB1KQ1R w KQkq - 0 1").unwrap();
+        assert_eq!(board.get_rank(Rank::First), expected.get_rank(Rank::First));
+        assert_eq!(board.pieces(PieceType::Pawn).count(), 15);
+        assert_eq!(board.occupied(), expected.occupied());
+        assert_eq!(board.hash(), expected.hash());
+        assert_eq!(
+            board.gen_legal_moves().len(),
+            expected.gen_legal_moves().len()
+        );
+    }
+
+    #[test]
+    fn make_unmake() {
+        for (fen, uci, after) in [
+            // quiet move and double push (sets en passant)
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "g1f3",
+                "rnbqkbnr/pppppppp/8/8/8/5N2/PPPPPPPP/RNBQKB1R b KQkq - 1 1",
+            ),
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                "e2e4",
+                "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1",
+            ),
+            // en passant
+            (
+                "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 2",
+                "e5d6",
+                "4k3/8/3P4/8/8/8/8/4K3 b - - 0 2",
+            ),
+            // castles, standard and Chess960
+            (
+                "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 3 10",
+                "e1g1",
+                "r3k2r/8/8/8/8/8/8/R4RK1 b kq - 4 10",
+            ),
+            (
+                "r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 3 10",
+                "e8c8",
+                "2kr3r/8/8/8/8/8/8/R3K2R w KQ - 4 11",
+            ),
+            (
+                "1r2k1r1/pppppppp/8/8/8/8/PPPPPPPP/RK4R1 w GAgb - 0 1",
+                "b1a1",
+                "1r2k1r1/pppppppp/8/8/8/8/PPPPPPPP/2KR2R1 b kq - 1 1",
+            ),
+            // capture of rook removes castling rights of both sides
+            (
+                "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 3 10",
+                "a1a8",
+                "R3k2r/8/8/8/8/8/8/4K2R b Kk - 0 10",
+            ),
+            // promotions, with and without capture
+            (
+                "1r2k3/P7/8/8/8/8/8/4K3 w - - 5 30",
+                "a7a8q",
+                "Qr2k3/8/8/8/8/8/8/4K3 b - - 0 30",
+            ),
+            (
+                "1r2k3/P7/8/8/8/8/8/4K3 w - - 5 30",
+                "a7b8n",
+                "1N2k3/8/8/8/8/8/8/4K3 b - - 0 30",
+            ),
+        ] {
+            let mut board = Board::from_fen(fen).unwrap();
+            let before = board.clone();
+            let mv: ChessMove = uci.parse().unwrap();
+
+            board.make_move(mv);
+            assert_eq!(board.to_fen(), after, "{} {}", fen, uci);
+            assert_eq!(board.hash(), board.compute_hash(), "{} {}", fen, uci);
+            assert_eq!(board.hash(), Board::from_fen(after).unwrap().hash());
+
+            assert_eq!(board.unmake_move(), Some(mv));
+            assert_eq!(board, before, "{} {}", fen, uci);
+            assert_eq!(board.hash(), board.compute_hash());
+            assert_eq!(board.unmake_move(), None);
+        }
+
+        // whole game is undone back to start
+        let mut board = Board::deafult();
+        let start = board.clone();
+        for uci in [
+            "e2e4", "d7d5", "e4d5", "c7c5", "d5c6", "d8d2", "e1d2", "g8f6", "c6b7",
+        ] {
+            board.make_move(uci.parse().unwrap());
+        }
+        board.make_move("f6e4".parse().unwrap());
+        board.make_move("b7a8q".parse().unwrap());
+        assert_eq!(board.undo_n(20), 11);
+        assert_eq!(board, start);
+    }
+
+    #[test]
+    fn en_passant_needs_pawn() {
+        // en passant square set by hand, but d5 holds knight
+        let mut board = Board::from_fen("4k3/8/8/3NP3/8/8/8/4K3 w - - 0 1").unwrap();
+        board.en_passant = Some(Square::D6);
+        let mv: ChessMove = "e5d6".parse().unwrap();
+        assert!(!board.gen_legal_moves().contains(&mv));
+        let info = board.move_info(mv).unwrap();
+        assert_eq!(info.kind, crate::MoveKind::Quiet);
+        assert_eq!(info.captured, None);
+        board.make_move(mv);
+        assert_eq!(
+            *board.get(Square::D5),
+            Some(Piece::new(PieceType::Knight, Color::White))
+        );
+
+        // no pawn at all behind en passant square
+        let mut board = Board::from_fen("4k3/8/8/4P3/8/8/8/4K3 w - - 0 1").unwrap();
+        board.en_passant = Some(Square::D6);
+        assert!(!board.gen_legal_moves().contains(&mv));
+    }
+
+    #[test]
+    fn side_to_move() {
+        let mut board = Board::deafult();
+        assert_eq!(board.side_to_move(), Color::White);
+        assert!(board
+            .gen_legal_moves()
+            .iter()
+            .all(|mv| board.get(mv.start).unwrap().color == Color::White));
+
+        board.make_move("e2e4".parse().unwrap());
+        assert_eq!(board.side_to_move(), Color::Black);
+        assert_eq!(board.fullmove_number(), 1);
+        let moves = board.gen_legal_moves();
+        assert_eq!(moves.len(), 20);
+        assert!(moves
+            .iter()
+            .all(|mv| board.get(mv.start).unwrap().color == Color::Black));
+
+        // fullmove number grows after black move
+        board.make_move("e7e5".parse().unwrap());
+        assert_eq!(board.side_to_move(), Color::White);
+        assert_eq!(board.fullmove_number(), 2);
+
+        board.unmake_move();
+        assert_eq!(board.side_to_move(), Color::Black);
+        assert_eq!(board.fullmove_number(), 1);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn checked_move() {
+        let mut board = Board::deafult();
+        assert_eq!(
+            board.make_move_checked("e7e5".parse().unwrap()),
+            Err(ChessError::WrongSideToMove)
+        );
+        assert_eq!(board.make_move_checked("e2e4".parse().unwrap()), Ok(()));
+        assert_eq!(board.side_to_move(), Color::Black);
     }
-    */
 }
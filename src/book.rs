@@ -0,0 +1,254 @@
+//! Reader of Polyglot opening books (`.bin` files), see [Book].
+
+use std::hash::BuildHasher;
+use std::path::Path;
+
+use crate::Board;
+use crate::ChessMove;
+use crate::PieceType;
+use crate::Square;
+
+/// Errors which can occur while reading [Book].
+/// Cloned [BookError::Io] keeps only kind and message of [std::io::Error],
+/// I/O errors are equal when their kinds are equal.
+#[derive(Debug)]
+pub enum BookError {
+    /// File can't be read.
+    Io(std::io::Error),
+    /// Book size isn't multiple of entry size (16 bytes).
+    InvalidLength,
+}
+
+impl std::fmt::Display for BookError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BookError::Io(e) => write!(f, "Invalid book: {}", e),
+            BookError::InvalidLength => write!(f, "Invalid book: size isn't multiple of 16."),
+        }
+    }
+}
+
+impl std::error::Error for BookError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BookError::Io(e) => Some(e),
+            BookError::InvalidLength => None,
+        }
+    }
+}
+
+impl Clone for BookError {
+    fn clone(&self) -> Self {
+        match self {
+            BookError::Io(e) => BookError::Io(std::io::Error::new(e.kind(), e.to_string())),
+            BookError::InvalidLength => BookError::InvalidLength,
+        }
+    }
+}
+
+impl PartialEq for BookError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (BookError::Io(a), BookError::Io(b)) => a.kind() == b.kind(),
+            (BookError::InvalidLength, BookError::InvalidLength) => true,
+            _ => false,
+        }
+    }
+}
+
+impl From<std::io::Error> for BookError {
+    fn from(e: std::io::Error) -> Self {
+        BookError::Io(e)
+    }
+}
+
+/// Move of [Book] with its weight (how often it should be played).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BookMove {
+    /// Legal move on the [Board], encoded same as in [Board::gen_legal_moves].
+    pub mv: ChessMove,
+    /// Weight of move from book, relative to other moves in same position.
+    pub weight: u16,
+}
+
+/// Entry of Polyglot book as it's stored in file.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Entry {
+    key: u64,
+    mv: u16,
+    weight: u16,
+}
+
+/// Polyglot opening book, positions are looked up by [Board::hash].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Book {
+    /// Entries sorted by key.
+    entries: Vec<Entry>,
+}
+
+impl Book {
+    /// Read [Book] from Polyglot `.bin` file.
+    pub fn open(path: impl AsRef<Path>) -> Result<Book, BookError> {
+        Book::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Read [Book] from bytes of Polyglot `.bin` file.
+    /// Every entry has 16 bytes (big-endian): key (8), move (2), weight (2) and learn (4).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Book, BookError> {
+        if !bytes.len().is_multiple_of(16) {
+            return Err(BookError::InvalidLength);
+        }
+
+        let mut entries: Vec<Entry> = bytes
+            .chunks_exact(16)
+            .map(|e| Entry {
+                key: u64::from_be_bytes(e[0..8].try_into().unwrap()),
+                mv: u16::from_be_bytes(e[8..10].try_into().unwrap()),
+                weight: u16::from_be_bytes(e[10..12].try_into().unwrap()),
+            })
+            .collect();
+        // books are sorted, but sorting again is cheap and makes lookup safe
+        entries.sort_by_key(|e| e.key);
+
+        Ok(Book { entries })
+    }
+
+    /// Get number of entries in [Book].
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if [Book] has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get all legal [BookMove]s for position on the [Board], sorted by weight (best first).
+    pub fn moves(&self, board: &Board) -> Vec<BookMove> {
+        let key = board.hash();
+        let start = self.entries.partition_point(|e| e.key < key);
+        let legal = board.gen_legal_moves();
+
+        let mut moves: Vec<BookMove> = self.entries[start..]
+            .iter()
+            .take_while(|e| e.key == key)
+            .filter_map(|e| {
+                let mv = decode_move(e.mv, board);
+                legal.contains(&mv).then_some(BookMove {
+                    mv,
+                    weight: e.weight,
+                })
+            })
+            .collect();
+        moves.sort_by_key(|m| std::cmp::Reverse(m.weight));
+        moves
+    }
+
+    /// Get move with the highest weight, [None] if position isn't in [Book].
+    pub fn best_move(&self, board: &Board) -> Option<ChessMove> {
+        self.moves(board).first().map(|m| m.mv)
+    }
+
+    /// Choose move randomly, moves with higher weight are chosen more often.
+    /// `random` is any random number (it's taken modulo sum of weights).
+    /// Return [None] if position isn't in [Book] or all moves have zero weight.
+    pub fn weighted_move(&self, board: &Board, random: u64) -> Option<ChessMove> {
+        let moves = self.moves(board);
+        let total: u64 = moves.iter().map(|m| m.weight as u64).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = random % total;
+        for m in moves {
+            if roll < m.weight as u64 {
+                return Some(m.mv);
+            }
+            roll -= m.weight as u64;
+        }
+        None
+    }
+
+    /// Same as [Book::weighted_move], random number is taken from random seed of std hasher.
+    pub fn random_move(&self, board: &Board) -> Option<ChessMove> {
+        let random = std::collections::hash_map::RandomState::new().hash_one(board.hash());
+        self.weighted_move(board, random)
+    }
+}
+
+/// Decode Polyglot move to [ChessMove].
+/// Move is stored in bits: to file (0-2), to row (3-5), from file (6-8), from row (9-11)
+/// and promotion (12-14), rows start from first rank. Castle is stored as king takes rook.
+fn decode_move(mv: u16, board: &Board) -> ChessMove {
+    let square = |file: u16, row: u16| Square(((7 - row) * 8 + file) as usize);
+    let start = square((mv >> 6) & 7, (mv >> 9) & 7);
+    let mut dest = square(mv & 7, (mv >> 3) & 7);
+    let promo = match (mv >> 12) & 7 {
+        1 => Some(PieceType::Knight),
+        2 => Some(PieceType::Bishop),
+        3 => Some(PieceType::Rook),
+        4 => Some(PieceType::Queen),
+        _ => None,
+    };
+
+    // king takes own rook, king goes to g or c file (Chess960 keeps king takes rook)
+    if let (Some(king), Some(rook)) = (board.get(start), board.get(dest)) {
+        if !board.is_chess960()
+            && king.piece_type == PieceType::King
+            && rook.piece_type == PieceType::Rook
+            && king.color == rook.color
+        {
+            let row = dest.0 - dest.0 % 8;
+            dest = if dest.0 > start.0 {
+                Square(row + 6)
+            } else {
+                Square(row + 2)
+            };
+        }
+    }
+
+    ChessMove::new(start, dest, promo)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Encode entry in Polyglot format.
+    fn entry(key: u64, mv: &str, weight: u16) -> Vec<u8> {
+        let b = mv.as_bytes();
+        let (ff, fr, tf, tr) = (b[0] - b'a', b[1] - b'1', b[2] - b'a', b[3] - b'1');
+        let mv = (tr as u16) << 3 | tf as u16 | (ff as u16) << 6 | (fr as u16) << 9;
+
+        let mut bytes = key.to_be_bytes().to_vec();
+        bytes.extend(mv.to_be_bytes());
+        bytes.extend(weight.to_be_bytes());
+        bytes.extend(0u32.to_be_bytes());
+        bytes
+    }
+
+    #[test]
+    fn read_book() {
+        let start = Board::deafult();
+        let castling = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+
+        let mut bytes = entry(start.hash(), "d2d4", 1);
+        bytes.extend(entry(start.hash(), "e2e4", 3));
+        bytes.extend(entry(castling.hash(), "e1h1", 1));
+        let book = Book::from_bytes(&bytes).unwrap();
+        assert_eq!(book.len(), 3);
+
+        let e4 = "e2e4".parse().unwrap();
+        let d4 = "d2d4".parse().unwrap();
+        assert_eq!(book.best_move(&start), Some(e4));
+        assert_eq!(book.weighted_move(&start, 2), Some(e4));
+        assert_eq!(book.weighted_move(&start, 3), Some(d4));
+        assert_eq!(book.best_move(&castling), Some("e1g1".parse().unwrap()));
+        assert_eq!(book.best_move(&Board::empty(crate::Color::White)), None);
+
+        assert!(matches!(
+            Book::from_bytes(&bytes[1..]),
+            Err(BookError::InvalidLength)
+        ));
+    }
+}
@@ -0,0 +1,103 @@
+//! Chess960 (Fischer Random Chess) starting positions, see [Board::chess960].
+
+use crate::Board;
+use crate::ChessError;
+use crate::PieceType;
+
+/// Placement of knights (N), rooks (R) and king (K) on five squares left by bishops and queen.
+const KNIGHTS_ROOKS_KING: [&str; 10] = [
+    "NNRKR", "NRNKR", "NRKNR", "NRKRN", "RNNKR", "RNKNR", "RNKRN", "RKNNR", "RKNRN", "RKRNN",
+];
+
+impl Board {
+    /// Setup [Board] to Chess960 starting position with index `position_index` (0..960),
+    /// numbered by Scharnagl scheme (518 is deafult chess position).
+    /// Castles of returned [Board] are encoded as king takes own rook.
+    pub fn chess960(position_index: u16) -> Result<Board, ChessError> {
+        if position_index >= 960 {
+            return Err(ChessError::OutOfBounds);
+        }
+        let mut n = position_index as usize;
+        let mut army = [None; 8];
+
+        // bishops on light and dark squares
+        army[n % 4 * 2 + 1] = Some(PieceType::Bishop);
+        n /= 4;
+        army[n % 4 * 2] = Some(PieceType::Bishop);
+        n /= 4;
+        // queen on n-th empty square
+        let queen = (0..8).filter(|&f| army[f].is_none()).nth(n % 6).unwrap();
+        army[queen] = Some(PieceType::Queen);
+        n /= 6;
+        // rest of squares in order
+        let empty: Vec<usize> = (0..8).filter(|&f| army[f].is_none()).collect();
+        for (f, c) in empty.into_iter().zip(KNIGHTS_ROOKS_KING[n].chars()) {
+            army[f] = PieceType::try_from_char(c).ok();
+        }
+
+        let mut board = Board::from_army(army.map(Option::unwrap));
+        board.chess960 = true;
+        Ok(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChessMove;
+    use crate::Color;
+    use crate::File;
+    use crate::KINGSIDE;
+    use crate::QUEENSIDE;
+
+    #[test]
+    fn starting_positions() {
+        let board = Board::chess960(518).unwrap();
+        assert_eq!(board.to_fen(), crate::STARTING_FEN);
+        assert_eq!(board.hash(), Board::deafult().hash());
+
+        assert_eq!(
+            Board::chess960(0).unwrap().to_fen(),
+            "bbqnnrkr/pppppppp/8/8/8/8/PPPPPPPP/BBQNNRKR w KQkq - 0 1"
+        );
+        let board = Board::chess960(959).unwrap();
+        assert_eq!(
+            board.to_fen(),
+            "rkrnnqbb/pppppppp/8/8/8/8/PPPPPPPP/RKRNNQBB w KQkq - 0 1"
+        );
+        assert_eq!(board.castling_rights(Color::White)[KINGSIDE], Some(File::C));
+        assert_eq!(
+            board.castling_rights(Color::Black)[QUEENSIDE],
+            Some(File::A)
+        );
+
+        let mut fens: Vec<String> = (0..960)
+            .map(|i| Board::chess960(i).unwrap().to_fen())
+            .collect();
+        fens.sort();
+        fens.dedup();
+        assert_eq!(fens.len(), 960);
+        assert_eq!(Board::chess960(960), Err(ChessError::OutOfBounds));
+    }
+
+    #[test]
+    fn castles() {
+        // king on b1 and rook on a1 castle long, king takes rook in UCI
+        let mut board =
+            Board::from_fen("1r2k1r1/pppppppp/8/8/8/8/PPPPPPPP/RK4R1 w GAgb - 0 1").unwrap();
+        assert!(board.is_chess960());
+        let mv = "b1a1".parse().unwrap();
+        assert!(board.gen_legal_moves().contains(&mv));
+        assert_eq!(mv.to_san(&board).as_deref(), Ok("O-O-O"));
+        board.make_move(mv);
+        assert_eq!(
+            board.to_fen(),
+            "1r2k1r1/pppppppp/8/8/8/8/PPPPPPPP/2KR2R1 b kq - 1 1"
+        );
+        board.unmake_move();
+        assert_eq!(
+            ChessMove::from_san("O-O", &board),
+            Ok("b1g1".parse().unwrap())
+        );
+    }
+}
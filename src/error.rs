@@ -0,0 +1,161 @@
+//! [ChessError], error type of the whole library.
+
+use crate::BookError;
+use crate::ChessMove;
+use crate::FenError;
+use crate::PgnError;
+use crate::SanError;
+use crate::Square;
+use crate::UciError;
+
+/// Errors which can be returned by library.
+/// Errors of parsers ([FenError], [SanError], ...) can be converted to it with `?`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChessError {
+    /// Index of [Square], [crate::File] or [crate::Rank] is outside the [crate::Board].
+    OutOfBounds,
+    /// Char or string isn't valid file, rank, piece or square.
+    InvalidNotation,
+    /// There is no piece on [Square] (e.g. on start square of move).
+    NoPieceOnSquare(Square),
+    /// Piece on start square of move isn't piece of side to move.
+    WrongSideToMove,
+    /// [ChessMove] isn't legal on the [crate::Board].
+    IllegalMove(ChessMove),
+    /// FEN string couldn't be parsed, see [FenError].
+    InvalidFen(FenError),
+    /// SAN move couldn't be parsed or isn't legal, see [SanError].
+    InvalidSan(SanError),
+    /// UCI move couldn't be parsed or isn't legal, see [UciError].
+    InvalidUci(UciError),
+    /// PGN game couldn't be read, see [PgnError].
+    InvalidPgn(PgnError),
+    /// Opening book couldn't be read, see [BookError].
+    InvalidBook(BookError),
+}
+
+impl std::fmt::Display for ChessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ChessError::OutOfBounds => write!(f, "Chess error: index is out of bounds."),
+            ChessError::InvalidNotation => write!(f, "Chess error: invalid notation."),
+            ChessError::NoPieceOnSquare(sq) => write!(f, "Chess error: no piece on {}.", sq),
+            ChessError::WrongSideToMove => write!(f, "Chess error: it's not turn of this piece."),
+            ChessError::IllegalMove(mv) => write!(f, "Chess error: move {} is illegal.", mv),
+            ChessError::InvalidFen(e) => write!(f, "{}", e),
+            ChessError::InvalidSan(e) => write!(f, "{}", e),
+            ChessError::InvalidUci(e) => write!(f, "{}", e),
+            ChessError::InvalidPgn(e) => write!(f, "{}", e),
+            ChessError::InvalidBook(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ChessError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ChessError::InvalidFen(e) => Some(e),
+            ChessError::InvalidSan(e) => Some(e),
+            ChessError::InvalidUci(e) => Some(e),
+            ChessError::InvalidPgn(e) => Some(e),
+            ChessError::InvalidBook(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<FenError> for ChessError {
+    fn from(e: FenError) -> Self {
+        ChessError::InvalidFen(e)
+    }
+}
+
+impl From<SanError> for ChessError {
+    fn from(e: SanError) -> Self {
+        ChessError::InvalidSan(e)
+    }
+}
+
+impl From<UciError> for ChessError {
+    fn from(e: UciError) -> Self {
+        ChessError::InvalidUci(e)
+    }
+}
+
+impl From<PgnError> for ChessError {
+    fn from(e: PgnError) -> Self {
+        ChessError::InvalidPgn(e)
+    }
+}
+
+impl From<BookError> for ChessError {
+    fn from(e: BookError) -> Self {
+        ChessError::InvalidBook(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn conversions() {
+        let e: ChessError = FenError::WrongFieldCount.into();
+        assert_eq!(e, ChessError::InvalidFen(FenError::WrongFieldCount));
+        let e: ChessError = SanError::AmbiguousMove.into();
+        assert_eq!(e, ChessError::InvalidSan(SanError::AmbiguousMove));
+        let e: ChessError = UciError::InvalidUci.into();
+        assert_eq!(e, ChessError::InvalidUci(UciError::InvalidUci));
+        let e: ChessError = PgnError::Unterminated.into();
+        assert_eq!(e, ChessError::InvalidPgn(PgnError::Unterminated));
+        assert!(e.source().is_some());
+        assert!(ChessError::OutOfBounds.source().is_none());
+
+        // I/O error of book is reachable through source chain
+        let e: ChessError = crate::Book::open("no/such/book.bin").unwrap_err().into();
+        assert_eq!(
+            e,
+            ChessError::InvalidBook(BookError::Io(std::io::ErrorKind::NotFound.into()))
+        );
+        let io = e.source().and_then(|e| e.source()).unwrap();
+        assert!(io.downcast_ref::<std::io::Error>().is_some());
+        let e: ChessError = BookError::InvalidLength.into();
+        assert_eq!(e.clone(), ChessError::InvalidBook(BookError::InvalidLength));
+        assert!(e.source().unwrap().source().is_none());
+
+        // `?` converts parser errors
+        fn parse(fen: &str) -> Result<crate::Board, ChessError> {
+            Ok(crate::Board::from_fen(fen)?)
+        }
+        assert_eq!(
+            parse("8/8/8/8 w - - 0 1"),
+            Err(ChessError::InvalidFen(FenError::InvalidPiecePlacement))
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            ChessError::OutOfBounds.to_string(),
+            "Chess error: index is out of bounds."
+        );
+        assert_eq!(
+            ChessError::NoPieceOnSquare(Square::E4).to_string(),
+            "Chess error: no piece on e4."
+        );
+        assert_eq!(
+            ChessError::IllegalMove("e2e5".parse().unwrap()).to_string(),
+            "Chess error: move e2e5 is illegal."
+        );
+        // wrapped errors are shown as they are
+        assert_eq!(
+            ChessError::InvalidSan(SanError::IllegalMove).to_string(),
+            SanError::IllegalMove.to_string()
+        );
+        assert_eq!(
+            ChessError::InvalidUci(UciError::InvalidUci).to_string(),
+            "UCI error: invalid UCI move."
+        );
+    }
+}
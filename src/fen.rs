@@ -0,0 +1,499 @@
+//! Import and export of [Board] in Forsyth–Edwards Notation (FEN).
+
+use crate::bitboard::color_index;
+use crate::board::back_rank;
+use crate::BitBoard;
+use crate::Board;
+use crate::Color;
+use crate::File;
+use crate::Piece;
+use crate::PieceType;
+use crate::Rank;
+use crate::Square;
+use crate::KINGSIDE;
+use crate::QUEENSIDE;
+
+/// FEN of the deafult chess position.
+pub const STARTING_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+/// Errors which can occur while parsing FEN with [Board::from_fen].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FenError {
+    /// FEN must have 6 fields (or 4, then clocks are set to deafult).
+    WrongFieldCount,
+    /// Piece placement field isn't made of 8 ranks with 8 squares each.
+    InvalidPiecePlacement,
+    /// Side to move isn't "w" or "b".
+    InvalidSideToMove,
+    /// Each side must have exactly one king.
+    InvalidKingCount,
+    /// Pawn stands on first or eighth rank.
+    PawnOnBackRank,
+    /// Castling field isn't "-" or rights ("KQkq" or rook files) of king and rook on back rank.
+    InvalidCastling,
+    /// En passant field isn't "-" or empty square on third or sixth rank skipped by pawn,
+    /// which just moved two squares.
+    InvalidEnPassant,
+    /// Halfmove clock isn't a number.
+    InvalidHalfmoveClock,
+    /// Fullmove number isn't a positive number.
+    InvalidFullmoveNumber,
+}
+
+impl std::fmt::Display for FenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            FenError::WrongFieldCount => "wrong number of fields",
+            FenError::InvalidPiecePlacement => "invalid piece placement",
+            FenError::InvalidSideToMove => "invalid side to move",
+            FenError::InvalidKingCount => "each side must have exactly one king",
+            FenError::PawnOnBackRank => "pawn on first or eighth rank",
+            FenError::InvalidCastling => "invalid castling rights",
+            FenError::InvalidEnPassant => "invalid en passant square",
+            FenError::InvalidHalfmoveClock => "invalid halfmove clock",
+            FenError::InvalidFullmoveNumber => "invalid fullmove number",
+        };
+        write!(f, "Invalid FEN: {}.", msg)
+    }
+}
+
+impl std::error::Error for FenError {}
+
+impl Board {
+    /// Create [Board] from FEN string.
+    /// Halfmove clock and fullmove number can be omitted (deafult is 0 and 1).
+    pub fn from_fen(fen: &str) -> Result<Board, FenError> {
+        let fields: Vec<&str> = fen.split_whitespace().collect();
+        if fields.len() != 6 && fields.len() != 4 {
+            return Err(FenError::WrongFieldCount);
+        }
+
+        let turn = match fields[1] {
+            "w" => Color::White,
+            "b" => Color::Black,
+            _ => return Err(FenError::InvalidSideToMove),
+        };
+
+        let mut board = Board::empty(turn);
+
+        // piece placement, first rank in FEN is eighth rank
+        let ranks: Vec<&str> = fields[0].split('/').collect();
+        if ranks.len() != 8 {
+            return Err(FenError::InvalidPiecePlacement);
+        }
+        for (rank_i, rank) in ranks.into_iter().enumerate() {
+            let mut file_i = 0;
+            for c in rank.chars() {
+                if let Some(empty) = c.to_digit(10) {
+                    if !(1..=8).contains(&empty) {
+                        return Err(FenError::InvalidPiecePlacement);
+                    }
+                    file_i += empty as usize;
+                } else {
+                    let piece =
+                        Piece::try_from_char(c).map_err(|_| FenError::InvalidPiecePlacement)?;
+                    if file_i > 7 {
+                        return Err(FenError::InvalidPiecePlacement);
+                    }
+                    board.place_piece(Square(rank_i * 8 + file_i), Some(piece));
+                    file_i += 1;
+                }
+            }
+            if file_i != 8 {
+                return Err(FenError::InvalidPiecePlacement);
+            }
+        }
+
+        for color in [Color::White, Color::Black] {
+            if (board.pieces(PieceType::King) & board.color_pieces(color)).count() != 1 {
+                return Err(FenError::InvalidKingCount);
+            }
+        }
+        if !(board.pieces(PieceType::Pawn) & (BitBoard::RANK_1 | BitBoard::RANK_8)).is_empty() {
+            return Err(FenError::PawnOnBackRank);
+        }
+
+        // castling, X-FEN (KQkq) and Shredder-FEN (rook files, e.g. HAha)
+        board.castling = [[None; 2]; 2];
+        if fields[2] != "-" {
+            for c in fields[2].chars() {
+                let color = if c.is_ascii_uppercase() {
+                    Color::White
+                } else {
+                    Color::Black
+                };
+                let king = back_rank_files(&board, color, PieceType::King)
+                    .next()
+                    .ok_or(FenError::InvalidCastling)?;
+                let mut rooks = back_rank_files(&board, color, PieceType::Rook);
+                let rook = match c.to_ascii_lowercase() {
+                    // outermost rook on the side of king
+                    'k' => rooks.filter(|&f| f > king).last(),
+                    'q' => rooks.find(|&f| f < king),
+                    'a'..='h' => {
+                        let file = c.to_ascii_lowercase() as usize - 'a' as usize;
+                        rooks.find(|&f| f == file && f != king)
+                    }
+                    _ => None,
+                }
+                .ok_or(FenError::InvalidCastling)?;
+
+                let side = if rook > king { KINGSIDE } else { QUEENSIDE };
+                let right = &mut board.castling[color_index(color)][side];
+                // same right written twice (e.g. "KK" or "KH")
+                if right.is_some() {
+                    return Err(FenError::InvalidCastling);
+                }
+                *right = File::try_from_usize(rook).ok();
+                // castling king or rook outside of standard squares
+                if king != 4 || rook != [7, 0][side] {
+                    board.chess960 = true;
+                }
+            }
+        }
+
+        // en passant
+        if fields[3] != "-" {
+            let sq: Square = fields[3].parse().map_err(|_| FenError::InvalidEnPassant)?;
+            let expected = if turn == Color::White {
+                Rank::Sixth
+            } else {
+                Rank::Third
+            };
+            if sq.get_rank() != Ok(expected) {
+                return Err(FenError::InvalidEnPassant);
+            }
+            // pawn which just moved two squares stands in front of skipped square,
+            // skipped square and square where pawn started are empty
+            let (pawn, start) = match turn {
+                Color::White => (Square(sq.0 + 8), Square(sq.0 - 8)),
+                Color::Black => (Square(sq.0 - 8), Square(sq.0 + 8)),
+            };
+            if *board.get(pawn) != Some(Piece::new(PieceType::Pawn, turn.get_inverse()))
+                || board.get(sq).is_some()
+                || board.get(start).is_some()
+            {
+                return Err(FenError::InvalidEnPassant);
+            }
+            board.en_passant = Some(sq);
+        }
+
+        // clocks
+        if fields.len() == 6 {
+            board.halfmove_clock = fields[4]
+                .parse()
+                .map_err(|_| FenError::InvalidHalfmoveClock)?;
+            board.fullmove_number = fields[5]
+                .parse()
+                .map_err(|_| FenError::InvalidFullmoveNumber)?;
+            if board.fullmove_number == 0 {
+                return Err(FenError::InvalidFullmoveNumber);
+            }
+        }
+
+        board.hash = board.compute_hash();
+        Ok(board)
+    }
+
+    /// Export [Board] to FEN string.
+    pub fn to_fen(&self) -> String {
+        let mut fen = String::new();
+
+        // piece placement
+        for rank_i in 0..8 {
+            let mut empty = 0;
+            for file_i in 0..8 {
+                match self.get(Square(rank_i * 8 + file_i)) {
+                    Some(piece) => {
+                        if empty > 0 {
+                            fen.push_str(&empty.to_string());
+                            empty = 0;
+                        }
+                        fen.push(piece.to_char());
+                    }
+                    None => empty += 1,
+                }
+            }
+            if empty > 0 {
+                fen.push_str(&empty.to_string());
+            }
+            if rank_i < 7 {
+                fen.push('/');
+            }
+        }
+
+        // side to move
+        fen.push(' ');
+        fen.push(match self.turn {
+            Color::White => 'w',
+            Color::Black => 'b',
+        });
+
+        // castling
+        fen.push(' ');
+        let mut castling = String::new();
+        for color in [Color::White, Color::Black] {
+            for (side, right) in self.castling[color_index(color)].iter().enumerate() {
+                // Board keeps only rights with king and rook on back rank
+                let Some(file) = right else {
+                    continue;
+                };
+                // K or Q when castling rook is outermost, otherwise file of rook
+                let mut rooks = back_rank_files(self, color, PieceType::Rook);
+                let outermost = match side {
+                    KINGSIDE => rooks.last(),
+                    _ => rooks.next(),
+                };
+                let c = match (outermost == Some(file.to_usize()), side) {
+                    (true, KINGSIDE) => 'k',
+                    (true, _) => 'q',
+                    (false, _) => file.to_char(),
+                };
+                castling.push(match color {
+                    Color::White => c.to_ascii_uppercase(),
+                    Color::Black => c,
+                });
+            }
+        }
+        if castling.is_empty() {
+            castling.push('-');
+        }
+        fen.push_str(&castling);
+
+        // en passant
+        fen.push(' ');
+        match self.en_passant {
+            Some(sq) => fen.push_str(&sq.to_string()),
+            None => fen.push('-'),
+        }
+
+        // clocks
+        fen.push_str(&format!(
+            " {} {}",
+            self.halfmove_clock, self.fullmove_number
+        ));
+
+        fen
+    }
+}
+
+/// Get files of pieces of [PieceType] and [Color] standing on back rank of that color.
+fn back_rank_files(
+    board: &Board,
+    color: Color,
+    piece_type: PieceType,
+) -> impl Iterator<Item = usize> {
+    let row = back_rank(color);
+    (board.pieces(piece_type) & board.color_pieces(color))
+        .iter()
+        .filter(move |sq| sq.0 / 8 == row)
+        .map(|sq| sq.0 % 8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for fen in [
+            STARTING_FEN,
+            // kiwipete
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            // en passant square and clocks
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3",
+            "8/8/4k3/8/2pP4/8/8/4K3 b - d3 0 45",
+            "4k3/8/8/8/8/8/8/R3K2R b KQ - 37 80",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert_eq!(board.to_fen(), fen);
+            assert_eq!(board.hash(), board.compute_hash());
+        }
+
+        let board = Board::from_fen("8/8/4k3/8/2pP4/8/8/4K3 b - d3 12 45").unwrap();
+        assert_eq!(board.en_passant(), Some(Square::D3));
+        assert_eq!(board.side_to_move(), Color::Black);
+        assert_eq!(board.halfmove_clock(), 12);
+        assert_eq!(board.fullmove_number(), 45);
+
+        // clocks can be omitted
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/4K3 w - -").unwrap();
+        assert_eq!(board.to_fen(), "4k3/8/8/8/8/8/8/4K3 w - - 0 1");
+    }
+
+    #[test]
+    fn shredder_castling() {
+        // rook files of standard setup don't switch to Chess960
+        let board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w HAha - 0 1").unwrap();
+        assert!(!board.is_chess960());
+        assert_eq!(board.to_fen(), "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        let castles: Vec<crate::ChessMove> = board
+            .gen_legal_moves()
+            .into_iter()
+            .filter(|mv| board.move_info(*mv).is_some_and(|info| info.is_castle()))
+            .collect();
+        assert_eq!(castles, ["e1g1".parse().unwrap(), "e1c1".parse().unwrap()]);
+
+        let board = Board::from_fen("1r2k1r1/8/8/8/8/8/8/RK4R1 w GAgb - 0 1").unwrap();
+        assert!(board.is_chess960());
+    }
+
+    #[test]
+    fn invalid_fen() {
+        let error = |fen| Board::from_fen(fen).unwrap_err();
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - - 0"),
+            FenError::WrongFieldCount
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/4K3 w - - 0 1"),
+            FenError::InvalidPiecePlacement
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K4 w - - 0 1"),
+            FenError::InvalidPiecePlacement
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K2 w - - 0 1"),
+            FenError::InvalidPiecePlacement
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K2X w - - 0 1"),
+            FenError::InvalidPiecePlacement
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3R w - - 0 1"),
+            FenError::InvalidPiecePlacement
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K03 w - - 0 1"),
+            FenError::InvalidPiecePlacement
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 x - - 0 1"),
+            FenError::InvalidSideToMove
+        );
+        assert_eq!(
+            error("8/8/8/8/8/8/8/8 w - - 0 1"),
+            FenError::InvalidKingCount
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"),
+            FenError::InvalidKingCount
+        );
+        assert_eq!(
+            error("P3k3/8/8/8/8/8/8/4K3 w - - 0 1"),
+            FenError::PawnOnBackRank
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K2p w - - 0 1"),
+            FenError::PawnOnBackRank
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K2R w X - 0 1"),
+            FenError::InvalidCastling
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K2R w Q - 0 1"),
+            FenError::InvalidCastling
+        );
+        assert_eq!(
+            error("r3k3/8/8/8/8/8/8/4K2R w KKqq - 0 1"),
+            FenError::InvalidCastling
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K2R w KH - 0 1"),
+            FenError::InvalidCastling
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - e9 0 1"),
+            FenError::InvalidEnPassant
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - e3 0 1"),
+            FenError::InvalidEnPassant
+        );
+        // no pawn to capture, or square behind pawn is occupied
+        assert_eq!(
+            error("4k3/8/8/3NP3/8/8/8/4K3 w - d6 0 1"),
+            FenError::InvalidEnPassant
+        );
+        assert_eq!(
+            error("4k3/8/8/4P3/8/8/8/4K3 w - d6 0 1"),
+            FenError::InvalidEnPassant
+        );
+        assert_eq!(
+            error("4k3/3n4/8/3pP3/8/8/8/4K3 w - d6 0 1"),
+            FenError::InvalidEnPassant
+        );
+        assert_eq!(
+            error("4k3/8/3n4/3pP3/8/8/8/4K3 w - d6 0 1"),
+            FenError::InvalidEnPassant
+        );
+        assert_eq!(
+            error("4k3/8/8/8/4p3/8/8/4K3 b - d3 0 1"),
+            FenError::InvalidEnPassant
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - - x 1"),
+            FenError::InvalidHalfmoveClock
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - - 0 0"),
+            FenError::InvalidFullmoveNumber
+        );
+        assert_eq!(
+            error("4k3/8/8/8/8/8/8/4K3 w - - 0 y"),
+            FenError::InvalidFullmoveNumber
+        );
+    }
+
+    #[test]
+    fn round_trip_of_built_board() {
+        let mut board = Board::empty(Color::White);
+        assert_eq!(board.to_fen(), "8/8/8/8/8/8/8/8 w - - 0 1");
+
+        board
+            .place_piece(Square::E1, Some(Piece::new(PieceType::King, Color::White)))
+            .place_piece(Square::E8, Some(Piece::new(PieceType::King, Color::Black)))
+            .place_piece(Square::H1, Some(Piece::new(PieceType::Rook, Color::White)));
+        let fen = board.to_fen();
+        assert_eq!(fen, "4k3/8/8/8/8/8/8/4K2R w - - 0 1");
+        assert_eq!(Board::from_fen(&fen).unwrap().to_fen(), fen);
+
+        // rook removed by hand, its right is removed too
+        let mut board = Board::deafult();
+        board.remove_piece(Square::H1);
+        assert_eq!(board.castling_rights(Color::White), [None, Some(File::A)]);
+        let fen = board.to_fen();
+        assert_eq!(
+            fen,
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBN1 w Qkq - 0 1"
+        );
+        assert_eq!(Board::from_fen(&fen).unwrap().hash(), board.hash());
+
+        // king moved by hand, rook put on other side of king
+        board
+            .remove_piece(Square::E8)
+            .place_piece(Square::E7, Some(Piece::new(PieceType::King, Color::Black)))
+            .remove_piece(Square::E1)
+            .remove_piece(Square::B1)
+            .place_piece(Square::B1, Some(Piece::new(PieceType::King, Color::White)));
+        let fen = board.to_fen();
+        assert_eq!(fen, "rnbq1bnr/ppppkppp/8/8/8/8/PPPPPPPP/RKBQ1BN1 w - - 0 1");
+        assert_eq!(board.hash(), board.compute_hash());
+        assert_eq!(Board::from_fen(&fen).unwrap().hash(), board.hash());
+
+        // rank edited directly
+        let mut board = Board::deafult();
+        board.get_mut_rank(Rank::Eighth)[0] = None;
+        assert_eq!(
+            Board::from_fen(&board.to_fen()).unwrap().hash(),
+            board.hash()
+        );
+        assert_eq!(
+            board.to_fen(),
+            "1nbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQk - 0 1"
+        );
+    }
+}
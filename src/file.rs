@@ -1,3 +1,5 @@
+use crate::ChessError;
+
 /// File enum for simple orientation on the board
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum File {
@@ -13,8 +15,8 @@ pub enum File {
 
 impl File {
     /// Function for converting [usize] to [File].
-    /// If conversion fails error message is returned in form of [str].
-    pub(crate) fn try_from_usize(num: usize) -> Result<Self, &'static str> {
+    /// If conversion fails [ChessError] is returned.
+    pub(crate) fn try_from_usize(num: usize) -> Result<Self, ChessError> {
         match num {
             0 => Ok(Self::A),
             1 => Ok(Self::B),
@@ -24,21 +26,41 @@ impl File {
             5 => Ok(Self::F),
             6 => Ok(Self::G),
             7 => Ok(Self::H),
-            _ => Err("File::try_from_usize endup with Error: usize to big."),
+            _ => Err(ChessError::OutOfBounds),
+        }
+    }
+
+    /// Function for converting [char] ('a'..='h') to [File].
+    /// If conversion fails [ChessError] is returned.
+    pub(crate) fn try_from_char(c: char) -> Result<Self, ChessError> {
+        match c {
+            'a'..='h' => File::try_from_usize(c as usize - 'a' as usize),
+            _ => Err(ChessError::InvalidNotation),
         }
     }
 
     /// Converts [File] to [usize].
-    pub(crate) fn to_usize(&self) -> usize {
+    pub(crate) fn to_usize(self) -> usize {
         match self {
-            Self::A => 7,
-            Self::B => 6,
-            Self::C => 5,
-            Self::D => 4,
-            Self::E => 3,
-            Self::F => 2,
-            Self::G => 1,
-            Self::H => 0,
+            Self::A => 0,
+            Self::B => 1,
+            Self::C => 2,
+            Self::D => 3,
+            Self::E => 4,
+            Self::F => 5,
+            Self::G => 6,
+            Self::H => 7,
         }
     }
+
+    /// Converts [File] to lowercase [char].
+    pub fn to_char(self) -> char {
+        (b'a' + self.to_usize() as u8) as char
+    }
+}
+
+impl std::fmt::Display for File {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
 }
@@ -0,0 +1,580 @@
+//! [Game] with tags and moves, which can be read from and written to PGN.
+
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::Board;
+use crate::ChessError;
+use crate::ChessMove;
+use crate::Color;
+use crate::FenError;
+use crate::SanError;
+
+/// Tags which are always written to PGN (in this order), known as seven tag roster.
+const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// Errors which can occur while reading PGN with [Game::from_pgn].
+#[derive(Clone, Debug, PartialEq)]
+pub enum PgnError {
+    /// Tag pair isn't in form `[Name "Value"]`.
+    InvalidTag,
+    /// Comment or variation isn't closed.
+    Unterminated,
+    /// FEN tag contains invalid FEN.
+    InvalidFen(FenError),
+    /// Move (in SAN) can't be played on the [Board].
+    InvalidMove(String, SanError),
+}
+
+impl std::fmt::Display for PgnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PgnError::InvalidTag => write!(f, "Invalid PGN: invalid tag pair."),
+            PgnError::Unterminated => write!(f, "Invalid PGN: unterminated comment or variation."),
+            PgnError::InvalidFen(e) => write!(f, "Invalid PGN: {}", e),
+            PgnError::InvalidMove(san, e) => write!(f, "Invalid PGN: move {}: {}", san, e),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {}
+
+/// Move played in [Game] with its PGN annotations.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GameMove {
+    /// Played move.
+    pub mv: ChessMove,
+    /// Numeric annotation glyphs (e.g. 1 is "!", 2 is "?").
+    pub nags: Vec<u8>,
+    /// Comment written after move.
+    pub comment: Option<String>,
+    /// Alternatives to this move (RAV), each played from position before this move.
+    /// They are read from PGN, but not written.
+    pub variations: Vec<Vec<GameMove>>,
+}
+
+/// [Game] stores tags, starting position and moves played from it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Game {
+    tags: Vec<(String, String)>,
+    start: Board,
+    board: Board,
+    moves: Vec<GameMove>,
+    /// Comment written before first move.
+    pub comment: Option<String>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Game::new()
+    }
+}
+
+impl Game {
+    /// Create new [Game] from deafult chess position.
+    pub fn new() -> Game {
+        Game::from_board(Board::deafult())
+    }
+
+    /// Create new [Game] starting from [Board] position.
+    /// Tags SetUp and FEN are added when position isn't deafult, tag Variant for Chess960.
+    pub fn from_board(board: Board) -> Game {
+        let mut game = Game {
+            tags: Vec::new(),
+            start: board.clone(),
+            board,
+            moves: Vec::new(),
+            comment: None,
+        };
+
+        for name in SEVEN_TAG_ROSTER {
+            game.set_tag(name, if name == "Result" { "*" } else { "?" });
+        }
+
+        let fen = game.start.to_fen();
+        if fen != crate::STARTING_FEN {
+            game.set_tag("SetUp", "1");
+            game.set_tag("FEN", &fen);
+        }
+        if game.start.is_chess960() {
+            game.set_tag("Variant", "Chess960");
+        }
+
+        game
+    }
+
+    /// Get value of tag.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Set (or replace) value of tag.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(n, _)| n == name) {
+            Some(tag) => tag.1 = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Get all tags in order.
+    pub fn tags(&self) -> &Vec<(String, String)> {
+        &self.tags
+    }
+
+    /// Get reference to current position.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Get reference to starting position.
+    pub fn start_board(&self) -> &Board {
+        &self.start
+    }
+
+    /// Get all played moves.
+    pub fn moves(&self) -> &Vec<GameMove> {
+        &self.moves
+    }
+
+    /// Make legal move on current position, return [Err] if move isn't legal.
+    pub fn make_move(&mut self, mv: ChessMove) -> Result<(), ChessError> {
+        self.board.try_make_move(mv)?;
+        self.moves.push(GameMove {
+            mv,
+            nags: Vec::new(),
+            comment: None,
+            variations: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Undo last move of [Game], return it or [None] if no move was played.
+    pub fn undo_move(&mut self) -> Option<GameMove> {
+        let game_move = self.moves.pop()?;
+        self.board.unmake_move();
+        Some(game_move)
+    }
+
+    /// Read first game from PGN.
+    /// Comments, NAGs and variations are kept (comment before first move of variation is dropped).
+    pub fn from_pgn(pgn: &str) -> Result<Game, PgnError> {
+        let mut chars = pgn.chars().peekable();
+
+        // tag pairs
+        let mut tags = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.next_if_eq(&'[').is_none() {
+                break;
+            }
+
+            let mut name = String::new();
+            while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '_') {
+                name.push(c);
+            }
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if name.is_empty() || chars.next() != Some('"') {
+                return Err(PgnError::InvalidTag);
+            }
+
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some('\\') => value.push(chars.next().ok_or(PgnError::InvalidTag)?),
+                    Some('"') => break,
+                    Some(c) => value.push(c),
+                    None => return Err(PgnError::InvalidTag),
+                }
+            }
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.next() != Some(']') {
+                return Err(PgnError::InvalidTag);
+            }
+
+            tags.push((name, value));
+        }
+
+        let mut start = match tags.iter().find(|(n, _)| n == "FEN") {
+            Some((_, fen)) => Board::from_fen(fen).map_err(PgnError::InvalidFen)?,
+            None => Board::deafult(),
+        };
+        if tags
+            .iter()
+            .any(|(n, v)| n == "Variant" && v.eq_ignore_ascii_case("chess960"))
+        {
+            start.set_chess960(true);
+        }
+        let mut game = Game::from_board(start);
+        for (name, value) in tags {
+            game.set_tag(&name, &value);
+        }
+
+        // movetext
+        if let Some(result) = read_line(
+            &mut chars,
+            &mut game.board,
+            &mut game.moves,
+            &mut game.comment,
+            false,
+        )? {
+            game.set_tag("Result", result);
+        }
+
+        Ok(game)
+    }
+
+    /// Write [Game] to PGN, seven tag roster goes first.
+    /// Only main line is written (with comments and NAGs).
+    pub fn to_pgn(&self) -> String {
+        let mut pgn = String::new();
+
+        let mut tags: Vec<&(String, String)> = Vec::new();
+        for name in SEVEN_TAG_ROSTER {
+            tags.extend(self.tags.iter().filter(|(n, _)| n == name));
+        }
+        tags.extend(
+            self.tags
+                .iter()
+                .filter(|(n, _)| !SEVEN_TAG_ROSTER.contains(&n.as_str())),
+        );
+        for (name, value) in tags {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            pgn.push_str(&format!("[{} \"{}\"]\n", name, value));
+        }
+        pgn.push('\n');
+
+        // movetext is made of tokens, which are wrapped to lines of max 80 chars
+        let mut tokens = Vec::new();
+        if let Some(comment) = &self.comment {
+            tokens.push(format!("{{{}}}", comment));
+        }
+
+        let mut board = self.start.clone();
+        let mut need_number = true;
+        for game_move in &self.moves {
+            // move number is kept on same line as move
+            // moves of Game were checked when they were made
+            let san = game_move.mv.to_san(&board).unwrap();
+            if board.side_to_move() == Color::White {
+                tokens.push(format!("{}. {}", board.fullmove_number(), san));
+            } else if need_number {
+                tokens.push(format!("{}... {}", board.fullmove_number(), san));
+            } else {
+                tokens.push(san);
+            }
+
+            board.make_move(game_move.mv);
+
+            for nag in &game_move.nags {
+                tokens.push(format!("${}", nag));
+            }
+            need_number = false;
+            if let Some(comment) = &game_move.comment {
+                tokens.push(format!("{{{}}}", comment));
+                need_number = true;
+            }
+        }
+        tokens.push(self.tag("Result").unwrap_or("*").to_string());
+
+        let mut line_len = 0;
+        for token in tokens {
+            if line_len > 0 && line_len + 1 + token.len() > 80 {
+                pgn.push('\n');
+                line_len = 0;
+            } else if line_len > 0 {
+                pgn.push(' ');
+                line_len += 1;
+            }
+            line_len += token.len();
+            pgn.push_str(&token);
+        }
+        pgn.push('\n');
+
+        pgn
+    }
+}
+
+/// Read moves of one line from PGN movetext and play them on [Board].
+/// Main line ends with result token (which is returned) or end of PGN,
+/// variation (`nested`) ends with `)`.
+fn read_line(
+    chars: &mut Peekable<Chars>,
+    board: &mut Board,
+    moves: &mut Vec<GameMove>,
+    comment: &mut Option<String>,
+    nested: bool,
+) -> Result<Option<&'static str>, PgnError> {
+    while let Some(c) = chars.next() {
+        match c {
+            '{' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => text.push(c),
+                        None => return Err(PgnError::Unterminated),
+                    }
+                }
+                add_comment(moves, comment, text.trim());
+            }
+            ';' => {
+                let text: String = chars.by_ref().take_while(|c| *c != '\n').collect();
+                add_comment(moves, comment, text.trim());
+            }
+            '(' => {
+                // variation replaces last move, so it's played from position before it
+                let mut from = board.clone();
+                from.unmake_move();
+                let mut variation = Vec::new();
+                read_line(chars, &mut from, &mut variation, &mut None, true)?;
+                if let Some(last) = moves.last_mut() {
+                    last.variations.push(variation);
+                }
+            }
+            ')' if nested => return Ok(None),
+            c if c.is_whitespace() => {}
+            _ => {
+                let mut token = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !"{}();".contains(*c)) {
+                    token.push(c);
+                }
+
+                if let Some(result) = ["1-0", "0-1", "1/2-1/2", "*"]
+                    .into_iter()
+                    .find(|r| *r == token && !nested)
+                {
+                    return Ok(Some(result));
+                }
+                read_token(&token, board, moves)?;
+            }
+        }
+    }
+
+    if nested {
+        Err(PgnError::Unterminated)
+    } else {
+        Ok(None)
+    }
+}
+
+/// Handle token from PGN movetext (move number, NAG or move in SAN).
+fn read_token(token: &str, board: &mut Board, moves: &mut Vec<GameMove>) -> Result<(), PgnError> {
+    // move number, e.g. "12." or "12...", can be glued to the move ("12.e4")
+    let token = match token.rfind('.') {
+        Some(i) if token[..i].chars().all(|c| c.is_ascii_digit() || c == '.') => &token[i + 1..],
+        _ => token,
+    };
+    if token.is_empty() {
+        return Ok(());
+    }
+
+    if let Some(nag) = token.strip_prefix('$') {
+        if let (Ok(nag), Some(last)) = (nag.parse(), moves.last_mut()) {
+            last.nags.push(nag);
+        }
+        return Ok(());
+    }
+
+    let mv = ChessMove::from_san(token, board)
+        .map_err(|e| PgnError::InvalidMove(token.to_string(), e))?;
+    board.make_move(mv);
+
+    // move suffix annotations are stored as NAGs
+    let suffix = token.trim_start_matches(|c| c != '!' && c != '?');
+    let nags = match suffix {
+        "!" => vec![1],
+        "?" => vec![2],
+        "!!" => vec![3],
+        "??" => vec![4],
+        "!?" => vec![5],
+        "?!" => vec![6],
+        _ => Vec::new(),
+    };
+
+    moves.push(GameMove {
+        mv,
+        nags,
+        comment: None,
+        variations: Vec::new(),
+    });
+    Ok(())
+}
+
+/// Add comment to last move (or to `comment` before first move if there is no move yet).
+fn add_comment(moves: &mut [GameMove], comment: &mut Option<String>, text: &str) {
+    let comment = match moves.last_mut() {
+        Some(last) => &mut last.comment,
+        None => comment,
+    };
+    match comment {
+        Some(c) => {
+            c.push(' ');
+            c.push_str(text);
+        }
+        None => *comment = Some(text.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SanError;
+
+    const PGN: &str = r#"[Event "Test"]
+[Site "Here \"quoted\""]
+[White "A"]
+[Black "B"]
+[Result "1-0"]
+
+{Opening comment} 1. e4 e5 $1 2. Nf3! {Knight out} (2. Bc4 {other} Nf6 (2... Bc5)) 2... Nc6 ; rest of line
+3.Bb5 a6 1-0
+"#;
+
+    #[test]
+    fn read_pgn() {
+        let game = Game::from_pgn(PGN).unwrap();
+        assert_eq!(game.tag("Event"), Some("Test"));
+        assert_eq!(game.tag("Site"), Some("Here \"quoted\""));
+        assert_eq!(game.tag("Date"), Some("?"));
+        assert_eq!(game.tag("Result"), Some("1-0"));
+        assert_eq!(game.comment.as_deref(), Some("Opening comment"));
+
+        let moves = game.moves();
+        assert_eq!(moves.len(), 6);
+        assert_eq!(moves[0].mv, "e2e4".parse().unwrap());
+        assert_eq!(moves[1].nags, vec![1]);
+        // suffix annotation is stored as NAG
+        assert_eq!(moves[2].nags, vec![1]);
+        assert_eq!(moves[2].comment.as_deref(), Some("Knight out"));
+        assert!(moves[1].variations.is_empty());
+        assert_eq!(moves[3].mv, "b8c6".parse().unwrap());
+        assert_eq!(moves[3].comment.as_deref(), Some("rest of line"));
+        assert_eq!(moves[4].mv, "f1b5".parse().unwrap());
+        assert_eq!(
+            game.board().to_fen(),
+            "r1bqkbnr/1ppp1ppp/p1n5/1B2p3/4P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 0 4"
+        );
+    }
+
+    #[test]
+    fn read_variations() {
+        let game = Game::from_pgn(PGN).unwrap();
+        // (2. Bc4 {other} Nf6 (2... Bc5)) is alternative to 2. Nf3
+        let variations = &game.moves()[2].variations;
+        assert_eq!(variations.len(), 1);
+        let variation = &variations[0];
+        assert_eq!(variation.len(), 2);
+        assert_eq!(variation[0].mv, "f1c4".parse().unwrap());
+        assert_eq!(variation[0].comment.as_deref(), Some("other"));
+        assert_eq!(variation[1].mv, "g8f6".parse().unwrap());
+        // nested variation is alternative to 2... Nf6
+        assert_eq!(variation[1].variations.len(), 1);
+        assert_eq!(variation[1].variations[0].len(), 1);
+        assert_eq!(variation[1].variations[0][0].mv, "f8c5".parse().unwrap());
+
+        // only main line is written
+        let pgn = game.to_pgn();
+        assert!(!pgn.contains('('));
+        let read = Game::from_pgn(&pgn).unwrap();
+        assert!(read.moves().iter().all(|m| m.variations.is_empty()));
+        assert_eq!(read.to_pgn(), pgn);
+
+        assert_eq!(
+            Game::from_pgn("1. e4 (1. d4 (1. c4 c5) d5 2. c4").unwrap_err(),
+            PgnError::Unterminated
+        );
+        assert!(matches!(
+            Game::from_pgn("1. e4 (1. e5) *"),
+            Err(PgnError::InvalidMove(_, SanError::IllegalMove))
+        ));
+    }
+
+    #[test]
+    fn write_pgn() {
+        let game = Game::from_pgn(PGN).unwrap();
+        let pgn = game.to_pgn();
+        assert_eq!(
+            pgn,
+            r#"[Event "Test"]
+[Site "Here \"quoted\""]
+[Date "?"]
+[Round "?"]
+[White "A"]
+[Black "B"]
+[Result "1-0"]
+
+{Opening comment} 1. e4 e5 $1 2. Nf3 $1 {Knight out} 2... Nc6 {rest of line}
+3. Bb5 a6 1-0
+"#
+        );
+        assert_eq!(Game::from_pgn(&pgn).unwrap().to_pgn(), pgn);
+    }
+
+    #[test]
+    fn black_to_move_and_variant() {
+        // game starting with black move needs FEN tag and "1..." move number
+        let board =
+            Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").unwrap();
+        let mut game = Game::from_board(board);
+        game.make_move("e7e5".parse().unwrap()).unwrap();
+        game.make_move("g1f3".parse().unwrap()).unwrap();
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[SetUp \"1\"]\n"));
+        assert!(
+            pgn.contains("[FEN \"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1\"]")
+        );
+        assert!(pgn.ends_with("\n1... e5 2. Nf3 *\n"));
+        assert_eq!(Game::from_pgn(&pgn).unwrap(), game);
+
+        // Chess960 castle is king takes own rook
+        let board =
+            Board::from_fen("1r2k1r1/pppppppp/8/8/8/8/PPPPPPPP/RK4R1 w GAgb - 0 1").unwrap();
+        let mut game = Game::from_board(board);
+        game.make_move("b1a1".parse().unwrap()).unwrap();
+        let pgn = game.to_pgn();
+        assert!(pgn.contains("[Variant \"Chess960\"]"));
+        assert!(pgn.ends_with("\n1. O-O-O *\n"));
+        let read = Game::from_pgn(&pgn).unwrap();
+        assert!(read.board().is_chess960());
+        assert_eq!(read.moves()[0].mv, "b1a1".parse().unwrap());
+
+        // Variant tag alone switches default position to Chess960
+        let game = Game::from_pgn("[Variant \"chess960\"]\n\n1. e4 *").unwrap();
+        assert!(game.start_board().is_chess960());
+    }
+
+    #[test]
+    fn results_and_errors() {
+        for result in ["1-0", "0-1", "1/2-1/2", "*"] {
+            let game = Game::from_pgn(&format!("1. e4 {}", result)).unwrap();
+            assert_eq!(game.tag("Result"), Some(result));
+        }
+        assert_eq!(
+            Game::from_pgn("[Event \"x\"").unwrap_err(),
+            PgnError::InvalidTag
+        );
+        assert_eq!(
+            Game::from_pgn("[Event x]").unwrap_err(),
+            PgnError::InvalidTag
+        );
+        assert_eq!(
+            Game::from_pgn("1. e4 {open").unwrap_err(),
+            PgnError::Unterminated
+        );
+        assert_eq!(
+            Game::from_pgn("1. e4 (1. d4").unwrap_err(),
+            PgnError::Unterminated
+        );
+        assert_eq!(
+            Game::from_pgn("[FEN \"bad\"]").unwrap_err(),
+            PgnError::InvalidFen(FenError::WrongFieldCount)
+        );
+        assert_eq!(
+            Game::from_pgn("1. e5").unwrap_err(),
+            PgnError::InvalidMove("e5".to_string(), SanError::IllegalMove)
+        );
+    }
+}
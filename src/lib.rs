@@ -13,9 +13,26 @@
 mod color;
 pub use crate::color::*;
 
+mod error;
+pub use crate::error::*;
+
+mod bitboard;
+pub use crate::bitboard::*;
+
+pub mod attacks;
+
+mod zobrist;
+pub use crate::zobrist::*;
+
+mod book;
+pub use crate::book::*;
+
 mod board;
 pub use crate::board::*;
 
+mod chess960;
+pub use crate::chess960::*;
+
 mod file;
 pub use crate::file::*;
 
@@ -34,6 +51,36 @@ pub use crate::chess_move::*;
 mod square;
 pub use crate::square::*;
 
+mod fen;
+pub use crate::fen::*;
+
+mod render;
+pub use crate::render::*;
+
+mod status;
+pub use crate::status::*;
+
+mod move_info;
+pub use crate::move_info::*;
+
+mod outcome;
+pub use crate::outcome::*;
+
+mod san;
+pub use crate::san::*;
+
+mod uci;
+pub use crate::uci::*;
+
+mod game;
+pub use crate::game::*;
+
+mod perft;
+pub use crate::perft::*;
+
+mod attackers;
+pub use crate::attackers::*;
+
 // old code:
 /*
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -1,6 +1,12 @@
 //! The moust interesting and the moust complex(spaghetti), part of the library.
 
-use crate::board;
+use crate::attackers::attackers_of;
+use crate::attacks;
+use crate::bitboard::color_index;
+use crate::bitboard::piece_index;
+use crate::board::back_rank;
+use crate::board::castle_dests;
+use crate::BitBoard;
 use crate::Board;
 use crate::ChessMove;
 use crate::Color;
@@ -8,6 +14,7 @@ use crate::Piece;
 use crate::PieceType;
 use crate::Rank;
 use crate::Square;
+use crate::KINGSIDE;
 
 /// Masks represents differnet types of generating moves via gen_move() function.
 #[derive(Clone, Copy, PartialEq)]
@@ -17,6 +24,15 @@ pub enum Mask {
     Both,
 }
 
+impl From<Color> for Mask {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::White => Mask::White,
+            Color::Black => Mask::Black,
+        }
+    }
+}
+
 impl Mask {
     /// Checks if [Color] is not filtered out.
     fn compare(&self, other: Color) -> bool {
@@ -41,313 +57,300 @@ impl Mask {
 }
 
 impl Board {
-    /// Helper for creating [ChessMove]s inside gen_moves_raw.
-    pub(crate) fn gen_moves_raw_temp(
-        &self,
-        piece_raw: &Option<Piece>,
-        piece: &Piece,
-        moves: &mut Vec<ChessMove>,
-        createfn: fn(
-            &Board,
-            &Option<Piece>,
-            i32,
-            Option<PieceType>,
-        ) -> Result<ChessMove, &'static str>,
-    ) {
-        for m in 1..=8 {
-            if let Ok(pot) = createfn(self, piece_raw, m as i32, None) {
-                if let Some(p) = self.get(pot.dest) {
-                    if p.color != piece.color {
-                        moves.push(pot);
-                    }
-                    break;
-                } else {
-                    moves.push(pot);
-                }
+    /// Generate castles of king standing on [Square] (FIDE and Chess960 rules).
+    /// Castling rook must keep its right, squares passed by king and rook must be empty
+    /// (except of them) and king can't be in check, pass through attacked square or end in check.
+    fn gen_castles(&self, square: Square, king: &Piece, moves: &mut Vec<ChessMove>) {
+        let row = back_rank(king.color);
+        if square.0 / 8 != row {
+            return;
+        }
+        let rook = Some(Piece::new(PieceType::Rook, king.color));
+        let enemy = king.color.get_inverse();
+        let occupied = self.occupied();
+
+        for (side, right) in self.castling[color_index(king.color)].iter().enumerate() {
+            let Some(file) = right else {
+                continue;
+            };
+            let rook_sq = Square(row * 8 + file.to_usize());
+            let kingside = side == KINGSIDE;
+            if *self.get(rook_sq) != rook || (rook_sq.0 > square.0) != kingside {
+                continue;
+            }
+
+            let (king_dest, rook_dest) = castle_dests(king.color, kingside);
+            let king_path = span(square, king_dest);
+            let blockers =
+                occupied & !BitBoard::from_square(square) & !BitBoard::from_square(rook_sq);
+            if !((king_path | span(rook_sq, rook_dest)) & blockers).is_empty()
+                || king_path
+                    .into_iter()
+                    .any(|sq| self.is_square_attacked(sq, enemy))
+            {
+                continue;
             }
+
+            let dest = if self.chess960 { rook_sq } else { king_dest };
+            moves.push(ChessMove::new(square, dest, None));
         }
     }
 
+    /// Create valid moves of side to move.
+    pub fn gen_legal_moves(&self) -> Vec<ChessMove> {
+        self.gen_moves(Mask::from(self.turn))
+    }
+
     /// Create valid moves from [Board], depends on [Mask].
     pub fn gen_moves(&self, mask: Mask) -> Vec<ChessMove> {
-        let mut moves = Vec::new();
+        self.gen_moves_masked(mask, BitBoard::FULL, BitBoard::FULL)
+    }
 
-        let mut board = self.clone();
-        board.clear_history();
+    /// Create valid moves of piece on [Square], empty if it isn't piece of side to move.
+    pub fn legal_moves_from(&self, sq: Square) -> Vec<ChessMove> {
+        self.gen_moves_masked(
+            Mask::from(self.turn),
+            BitBoard::from_square(sq),
+            BitBoard::FULL,
+        )
+    }
 
-        for mv in self.gen_moves_raw(mask) {
-            let mut test_board = board.clone();
-            test_board.make_move(mv);
+    /// Create valid moves of side to move, which go to [Square].
+    pub fn legal_moves_to(&self, sq: Square) -> Vec<ChessMove> {
+        self.gen_moves_masked(
+            Mask::from(self.turn),
+            BitBoard::FULL,
+            BitBoard::from_square(sq),
+        )
+    }
 
-            let mut check = false;
-            for tmv in test_board.gen_moves_raw(mask) {
-                if let Some(p) = test_board.get(tmv.dest) {
-                    if p.piece_type == PieceType::King {
-                        check = true;
-                        break;
-                    }
-                }
-            }
+    /// Create valid moves of all pieces of [PieceType] of side to move.
+    pub fn legal_moves_of(&self, piece_type: PieceType) -> Vec<ChessMove> {
+        self.gen_moves_masked(
+            Mask::from(self.turn),
+            self.pieces(piece_type),
+            BitBoard::FULL,
+        )
+    }
 
-            if !check {
-                moves.push(mv);
-            }
+    /// Create valid moves of pieces standing on `from` squares to `to` squares, depends on [Mask].
+    pub(crate) fn gen_moves_masked(
+        &self,
+        mask: Mask,
+        from: BitBoard,
+        to: BitBoard,
+    ) -> Vec<ChessMove> {
+        let mut moves = self.gen_moves_raw_masked(mask, from, to);
+        moves.retain(|mv| self.is_king_safe_after(*mv));
+        moves
+    }
+
+    /// Check if [ChessMove] (generated by gen_moves_raw) doesn't leave own king in check.
+    /// Move is applied only to copies of [BitBoard]s, [Board] itself isn't changed.
+    fn is_king_safe_after(&self, mv: ChessMove) -> bool {
+        // gen_moves_raw generates only moves of existing pieces
+        let piece = self.get(mv.start).unwrap();
+        let mut pieces = self.pieces;
+        let mut colors = self.colors;
+        let start = BitBoard::from_square(mv.start);
+        let dest = BitBoard::from_square(mv.dest);
+
+        if let Some(captured) = self.get(mv.dest).filter(|p| p.color != piece.color) {
+            pieces[piece_index(captured.piece_type)] &= !dest;
+            colors[color_index(captured.color)] &= !dest;
+        } else if let Some(victim) = self.en_passant_victim(mv) {
+            let taken = !BitBoard::from_square(victim);
+            pieces[piece_index(PieceType::Pawn)] &= taken;
+            colors[color_index(piece.color.get_inverse())] &= taken;
         }
 
-        let mut moves_rm_dp = Vec::new();
-        for m in moves {
-            if !moves_rm_dp.contains(&m) {
-                moves_rm_dp.push(m);
-            }
+        if let Some(rook_sq) = self.castle_rook(mv) {
+            // king and rook jump to their squares, rook may have been blocking check
+            let (king_dest, rook_dest) = castle_dests(piece.color, rook_sq.0 > mv.start.0);
+            let rook = BitBoard::from_square(rook_sq);
+            let moved = BitBoard::from_square(king_dest) | BitBoard::from_square(rook_dest);
+            pieces[piece_index(PieceType::King)] =
+                pieces[piece_index(PieceType::King)] & !start | BitBoard::from_square(king_dest);
+            pieces[piece_index(PieceType::Rook)] =
+                pieces[piece_index(PieceType::Rook)] & !rook | BitBoard::from_square(rook_dest);
+            colors[color_index(piece.color)] =
+                colors[color_index(piece.color)] & !start & !rook | moved;
+            return attackers_of(&pieces, &colors, king_dest, piece.color.get_inverse()).is_empty();
         }
 
-        moves_rm_dp
+        pieces[piece_index(piece.piece_type)] &= !start;
+        pieces[piece_index(mv.promo.unwrap_or(piece.piece_type))] |= dest;
+        colors[color_index(piece.color)] = colors[color_index(piece.color)] & !start | dest;
+
+        match (pieces[piece_index(PieceType::King)] & colors[color_index(piece.color)]).first() {
+            Some(king) => {
+                attackers_of(&pieces, &colors, king, piece.color.get_inverse()).is_empty()
+            }
+            None => true,
+        }
     }
 
     /// Create valid moves from [Board], but not take checks in to account. Depends on [Mask].
-    fn gen_moves_raw(&self, mask: Mask) -> Vec<ChessMove> {
+    pub(crate) fn gen_moves_raw(&self, mask: Mask) -> Vec<ChessMove> {
+        self.gen_moves_raw_masked(mask, BitBoard::FULL, BitBoard::FULL)
+    }
+
+    /// Same as gen_moves_raw, but only moves from `from_mask` to `to_mask` squares are generated.
+    fn gen_moves_raw_masked(
+        &self,
+        mask: Mask,
+        from_mask: BitBoard,
+        to_mask: BitBoard,
+    ) -> Vec<ChessMove> {
         let mut moves = Vec::new();
+        let occupied = self.occupied();
 
-        for u in 0..64usize {
-            let square = Square(u);
-            let piece_raw = self.get(square);
-
-            if let Some(piece) = piece_raw {
-                if mask.compare(piece.color) {
-                    match piece.piece_type {
-                        crate::PieceType::Pawn => {
-                            // decide if pawn stand on sevent or second rank (depends on color)
-                            let is_on_start = square.get_rank().unwrap()
-                                == if piece.color == Color::White {
-                                    Rank::Second
-                                } else {
-                                    Rank::Seventh
-                                };
-
-                            let is_on_end = square.get_rank().unwrap()
-                                == if piece.color == Color::White {
-                                    Rank::Seventh
-                                } else {
-                                    Rank::Second
-                                };
-
-                            // set pawn promo from self.pawn_promo
-                            let mut promo = match is_on_end {
-                                true => Some(self.pawn_promo),
-                                false => None,
-                            };
-
-                            // # pawn move up normal
-                            // pawn up (x1) move can be unwraped directly,
-                            // beacuse pawn can't get outside of board (promoting).
-                            let pot_up = ChessMove::up(self, piece_raw, 1, promo).unwrap();
-
-                            if *self.get(pot_up.dest) == None {
-                                moves.push(pot_up);
-                                // # pawn move up double
-                                if is_on_start {
-                                    moves.push(ChessMove::up(self, piece_raw, 2, promo).unwrap());
-                                }
-                            }
-
-                            // # pawn takes
-                            if let Ok(pot_up_left) = ChessMove::up_left(self, piece_raw, 1, promo) {
-                                if let Some(target) = *self.get(pot_up_left.dest) {
-                                    if target.color != piece.color {
-                                        moves.push(pot_up_left);
-                                    }
-                                } else if square.get_rank().unwrap()
-                                    == if piece.color == Color::White {
-                                        Rank::Fifth
-                                    } else {
-                                        Rank::Fourth
-                                    }
-                                {
-                                    // # pawn takes en passatns
-                                    // left check must be right idk why
-                                    if let Ok(left_check) =
-                                        ChessMove::right(self, piece_raw, 1, promo)
-                                    {
-                                        let maybe_en_target = self.get(left_check.dest);
-                                        if let Some(en_target) = maybe_en_target {
-                                            let previous_sq =
-                                                ChessMove::up(self, maybe_en_target, 2, None)
-                                                    .unwrap()
-                                                    .dest;
-                                            if let Some(previous_target) =
-                                                self.get_from_previous(previous_sq)
-                                            {
-                                                // takes en passatns
-                                                moves.push(pot_up_left);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            if let Ok(pot_up_right) = ChessMove::up_right(self, piece_raw, 1, promo)
-                            {
-                                if let Some(target) = *self.get(pot_up_right.dest) {
-                                    if target.color != piece.color {
-                                        moves.push(pot_up_right);
-                                    }
-                                } else if self.get_square(piece_raw).unwrap().get_rank().unwrap()
-                                    == if piece.color == Color::White {
-                                        Rank::Fifth
-                                    } else {
-                                        Rank::Fourth
-                                    }
-                                {
-                                    // # pawn takes en passatns
-                                    // right check must be left idk why
-                                    if let Ok(right_check) =
-                                        ChessMove::left(self, piece_raw, 1, promo)
-                                    {
-                                        let maybe_en_target = self.get(right_check.dest);
-                                        if let Some(en_target) = maybe_en_target {
-                                            let previous_sq =
-                                                ChessMove::down(self, maybe_en_target, 2, None)
-                                                    .unwrap()
-                                                    .dest;
-                                            if let Some(previous_target) =
-                                                self.get_from_previous(previous_sq)
-                                            {
-                                                moves.push(pot_up_right);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        crate::PieceType::Rook => {
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::up);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::down);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::left);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::right);
-                        }
-                        crate::PieceType::Bishop => {
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::up_left);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::up_right);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::down_left);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::down_right);
-                        }
-                        crate::PieceType::Queen => {
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::up);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::down);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::left);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::right);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::up_left);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::up_right);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::down_left);
-                            self.gen_moves_raw_temp(piece_raw, piece, &mut moves, ChessMove::down_right);
-                        }
-                        crate::PieceType::King => {
-                            for m in [
-                                ChessMove::up(self, piece_raw, 1, None),
-                                ChessMove::down(self, piece_raw, 1, None),
-                                ChessMove::left(self, piece_raw, 1, None),
-                                ChessMove::right(self, piece_raw, 1, None),
-                                ChessMove::up_left(self, piece_raw, 1, None),
-                                ChessMove::up_right(self, piece_raw, 1, None),
-                                ChessMove::down_left(self, piece_raw, 1, None),
-                                ChessMove::down_right(self, piece_raw, 1, None),
-                            ] {
-                                if let Ok(pot) = m {
-                                    if let Some(p) = self.get(pot.dest) {
-                                        if p.color != piece.color {
-                                            moves.push(pot);
-                                        }
-                                    } else {
-                                        moves.push(pot);
-                                    }
-                                }
-                            }
-
-                            if piece.color == Color::White {
-                                if !self.king_moved_w {
-                                    // short castle
-                                    if !self.right_rook_moved_w {
-                                        if *self.get(Square::F1) == None
-                                            && *self.get(Square::G1) == None
-                                        {
-                                            moves.push(
-                                                ChessMove::left(self, piece_raw, 2, None).unwrap(),
-                                            );
-                                        }
-                                    }
-                                    // long castle
-                                    if !self.left_rook_moved_w {
-                                        if *self.get(Square::D1) == None
-                                            && *self.get(Square::C1) == None
-                                        {
-                                            moves.push(
-                                                ChessMove::right(self, piece_raw, 2, None).unwrap(),
-                                            );
-                                        }
-                                    }
-                                }
-                            } else {
-                                if !self.king_moved_b {
-                                    // short castle
-                                    if !self.right_rook_moved_b {
-                                        if *self.get(Square::F8) == None
-                                            && *self.get(Square::F8) == None
-                                        {
-                                            moves.push(
-                                                ChessMove::right(self, piece_raw, 2, None).unwrap(),
-                                            );
-                                        }
-                                    }
-                                    // long castle
-                                    if !self.left_rook_moved_b {
-                                        if *self.get(Square::D8) == None
-                                            && *self.get(Square::C8) == None
-                                        {
-                                            moves.push(
-                                                ChessMove::left(self, piece_raw, 2, None).unwrap(),
-                                            );
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        crate::PieceType::Knight => {
-                            let pos = self.get_square(piece_raw).unwrap();
-                            for pot_m in [
-                                ChessMove::new(pos, Square(pos.0.abs_diff(17)), None),
-                                ChessMove::new(pos, Square(pos.0.abs_diff(15)), None),
-                                ChessMove::new(pos, Square(pos.0 + 17), None),
-                                ChessMove::new(pos, Square(pos.0 + 15), None),
-                                ChessMove::new(pos, Square(pos.0 + 10), None),
-                                ChessMove::new(pos, Square(pos.0.abs_diff(6)), None),
-                                ChessMove::new(pos, Square(pos.0 + 6), None),
-                                ChessMove::new(pos, Square(pos.0.abs_diff(10)), None),
-                            ] {
-                                if pot_m.dest.0 > 63 {
-                                    continue;
-                                } else if pos
-                                    .get_file()
-                                    .unwrap()
-                                    .to_usize()
-                                    .abs_diff(pot_m.dest.get_file().unwrap().to_usize())
-                                    > 3
-                                {
-                                    continue;
-                                }
-
-                                if let Some(m) = self.get(pot_m.dest) {
-                                    if m.color != piece.color {
-                                        moves.push(pot_m);
-                                    }
-                                } else {
-                                    moves.push(pot_m);
-                                }
-                            }
+        for color in [Color::White, Color::Black] {
+            if !mask.compare(color) {
+                continue;
+            }
+            let own = self.colors[color_index(color)];
+            let enemy = self.colors[color_index(color.get_inverse())];
+            let targets = !own & to_mask;
+
+            // pawns
+            // en passant square is on sixth (white) or third (black) rank
+            let (start_rank, en_passant_rank, last_rank, forward) = match color {
+                Color::White => (Rank::Second, Rank::Sixth, BitBoard::RANK_8, -8),
+                Color::Black => (Rank::Seventh, Rank::Third, BitBoard::RANK_1, 8),
+            };
+            // enemy pawn which skipped en passant square has to stand behind it
+            let en_passant = match self.en_passant {
+                Some(sq)
+                    if sq.get_rank() == Ok(en_passant_rank)
+                        && !occupied.contains(sq)
+                        && (self.pieces[piece_index(PieceType::Pawn)] & enemy)
+                            .contains(Square((sq.0 as i32 - forward) as usize)) =>
+                {
+                    BitBoard::from_square(sq)
+                }
+                _ => BitBoard::EMPTY,
+            };
+            for from in self.pieces[piece_index(PieceType::Pawn)] & own & from_mask {
+                let mut dests = attacks::pawn(color, from) & (enemy | en_passant);
+
+                // pawn on last rank (only placed there by hand) can't be pushed off the board
+                if last_rank.contains(from) {
+                    continue;
+                }
+                let one = Square((from.0 as i32 + forward) as usize);
+                if !occupied.contains(one) {
+                    dests |= BitBoard::from_square(one);
+                    let two = Square((one.0 as i32 + forward) as usize);
+                    if from.get_rank() == Ok(start_rank) && !occupied.contains(two) {
+                        dests |= BitBoard::from_square(two);
+                    }
+                }
+
+                for to in dests & to_mask {
+                    if last_rank.contains(to) {
+                        for promo in [
+                            PieceType::Queen,
+                            PieceType::Rook,
+                            PieceType::Bishop,
+                            PieceType::Knight,
+                        ] {
+                            moves.push(ChessMove::new(from, to, Some(promo)));
                         }
+                    } else {
+                        moves.push(ChessMove::new(from, to, None));
                     }
                 }
             }
+
+            // pieces
+            for piece_type in [
+                PieceType::Knight,
+                PieceType::Bishop,
+                PieceType::Rook,
+                PieceType::Queen,
+                PieceType::King,
+            ] {
+                for from in self.pieces[piece_index(piece_type)] & own & from_mask {
+                    let attacks = match piece_type {
+                        PieceType::Knight => attacks::knight(from),
+                        PieceType::Bishop => attacks::bishop(from, occupied),
+                        PieceType::Rook => attacks::rook(from, occupied),
+                        PieceType::Queen => attacks::queen(from, occupied),
+                        _ => attacks::king(from),
+                    };
+                    for to in attacks & targets {
+                        moves.push(ChessMove::new(from, to, None));
+                    }
+                }
+            }
+
+            // castles
+            if let Some(king) =
+                (self.pieces[piece_index(PieceType::King)] & own & from_mask).first()
+            {
+                let mut castles = Vec::new();
+                self.gen_castles(king, &Piece::new(PieceType::King, color), &mut castles);
+                moves.extend(castles.into_iter().filter(|mv| to_mask.contains(mv.dest)));
+            }
         }
 
         moves
     }
 }
+
+/// Get squares on rank between two [Square]s, both included.
+fn span(a: Square, b: Square) -> BitBoard {
+    let (low, high) = (a.0.min(b.0), a.0.max(b.0));
+    BitBoard((u64::MAX >> (63 - high)) & (u64::MAX << low))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_of_single_piece() {
+        let board = Board::deafult();
+        assert_eq!(board.legal_moves_from(Square::G1).len(), 2);
+        assert_eq!(board.legal_moves_from(Square::E1).len(), 0);
+        // pieces of side which isn't on the move have no legal moves
+        assert_eq!(board.legal_moves_from(Square::E7).len(), 0);
+        assert_eq!(
+            board.legal_moves_to(Square::E4),
+            vec![ChessMove::new(Square::E2, Square::E4, None)]
+        );
+        assert_eq!(board.legal_moves_of(PieceType::Knight).len(), 4);
+
+        // kiwipete, castles are generated from king square (g1 is reached also by rook)
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        assert_eq!(board.legal_moves_from(Square::E1).len(), 4);
+        assert_eq!(board.legal_moves_to(Square::G1).len(), 2);
+        let mut all = Vec::new();
+        for i in 0..64 {
+            all.extend(board.legal_moves_from(Square(i)));
+        }
+        let legal = board.gen_legal_moves();
+        assert_eq!(all.len(), legal.len());
+        assert!(all.iter().all(|mv| legal.contains(mv)));
+    }
+
+    #[test]
+    fn pawn_on_last_rank() {
+        // pawns on last rank can't be created by moves, but place_piece allows them
+        let mut board = Board::empty(Color::White);
+        board
+            .place_piece(Square::E1, Some(Piece::new(PieceType::King, Color::White)))
+            .place_piece(Square::E8, Some(Piece::new(PieceType::King, Color::Black)))
+            .place_piece(Square::A8, Some(Piece::new(PieceType::Pawn, Color::White)))
+            .place_piece(Square::H1, Some(Piece::new(PieceType::Pawn, Color::Black)));
+        assert_eq!(board.gen_legal_moves().len(), 5);
+        assert!(board.legal_moves_from(Square::A8).is_empty());
+        assert!(board
+            .gen_moves(Mask::Black)
+            .iter()
+            .all(|mv| mv.start == Square::E8));
+    }
+}
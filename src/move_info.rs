@@ -0,0 +1,137 @@
+//! [MoveInfo], [ChessMove] with moving piece, captured piece and [MoveKind].
+
+use crate::Board;
+use crate::ChessMove;
+use crate::Mask;
+use crate::Piece;
+use crate::PieceType;
+use crate::Square;
+
+/// Kind of [ChessMove].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveKind {
+    /// Move without capture (pawn single push included).
+    Quiet,
+    /// Pawn moving two squares from start rank.
+    DoublePush,
+    /// Move capturing piece on destination square.
+    Capture,
+    /// Pawn taking pawn which just made double push.
+    EnPassant,
+    /// King castling to g file (with rook from side of h file).
+    CastleKingside,
+    /// King castling to c file (with rook from side of a file).
+    CastleQueenside,
+    /// Pawn promoting on last rank (with or without capture).
+    Promotion,
+}
+
+/// [ChessMove] with information about it, so [Board] doesn't have to be inspected again.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveInfo {
+    /// Described move, castles are encoded same as in [Board::gen_legal_moves].
+    pub mv: ChessMove,
+    /// Piece which moves (pawn, when move is promotion).
+    pub piece: Piece,
+    /// Captured piece (pawn taken en passant included).
+    pub captured: Option<Piece>,
+    /// Kind of move, see [MoveKind].
+    pub kind: MoveKind,
+}
+
+impl MoveInfo {
+    /// Check if move captures piece.
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    /// Check if move is castle.
+    pub fn is_castle(&self) -> bool {
+        matches!(
+            self.kind,
+            MoveKind::CastleKingside | MoveKind::CastleQueenside
+        )
+    }
+}
+
+impl Board {
+    /// Get [MoveInfo] of [ChessMove] in current position.
+    /// Move isn't checked, return [None] only if there is no piece on start square.
+    pub fn move_info(&self, mv: ChessMove) -> Option<MoveInfo> {
+        let piece = (*self.get(mv.start))?;
+        let mut captured = *self.get(mv.dest);
+        let castle_rook = self.castle_rook(mv);
+        let en_passant = self.en_passant_victim(mv);
+
+        let kind = match piece.piece_type {
+            PieceType::Pawn if mv.promo.is_some() => MoveKind::Promotion,
+            PieceType::Pawn if mv.start.0.abs_diff(mv.dest.0) == 16 => MoveKind::DoublePush,
+            PieceType::Pawn if en_passant.is_some() => {
+                captured = en_passant.and_then(|sq| *self.get(sq));
+                MoveKind::EnPassant
+            }
+            // Chess960 castle is king takes own rook, it isn't capture
+            PieceType::King if castle_rook.is_some() => {
+                captured = None;
+                if castle_rook.is_some_and(|rook| rook.0 > mv.start.0) {
+                    MoveKind::CastleKingside
+                } else {
+                    MoveKind::CastleQueenside
+                }
+            }
+            _ if captured.is_some() => MoveKind::Capture,
+            _ => MoveKind::Quiet,
+        };
+
+        Some(MoveInfo {
+            mv,
+            piece,
+            captured,
+            kind,
+        })
+    }
+
+    /// Create valid moves of side to move with [MoveInfo].
+    pub fn gen_legal_moves_info(&self) -> Vec<MoveInfo> {
+        self.gen_moves_info(Mask::from(self.turn))
+    }
+
+    /// Create valid moves with [MoveInfo], depends on [Mask].
+    pub fn gen_moves_info(&self, mask: Mask) -> Vec<MoveInfo> {
+        self.gen_moves(mask)
+            .into_iter()
+            // generated moves are always moves of existing pieces
+            .map(|mv| self.move_info(mv).unwrap())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+
+    #[test]
+    fn kinds_of_moves() {
+        // kiwipete, 48 moves with 8 captures, 2 castles and 2 double pushes
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        let moves = board.gen_legal_moves_info();
+        let count = |kind| moves.iter().filter(|m| m.kind == kind).count();
+        assert_eq!(moves.len(), 48);
+        assert_eq!(count(MoveKind::Capture), 8);
+        assert_eq!(count(MoveKind::CastleKingside), 1);
+        assert_eq!(count(MoveKind::CastleQueenside), 1);
+        assert_eq!(count(MoveKind::DoublePush), 2);
+
+        let board = Board::from_fen("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1").unwrap();
+        let info = board.move_info("e5d6".parse().unwrap()).unwrap();
+        assert_eq!(info.kind, MoveKind::EnPassant);
+        assert_eq!(
+            info.captured,
+            Some(Piece::new(PieceType::Pawn, Color::Black))
+        );
+        assert_eq!(info.piece, Piece::new(PieceType::Pawn, Color::White));
+    }
+}
@@ -0,0 +1,106 @@
+//! Checked making of moves with [Board::try_make_move], which reports what happened.
+
+use crate::Board;
+use crate::ChessError;
+use crate::ChessMove;
+use crate::MoveKind;
+use crate::Piece;
+use crate::PieceType;
+
+/// What happened when [ChessMove] was made by [Board::try_make_move].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MoveOutcome {
+    /// Captured piece (pawn taken en passant included).
+    pub captured: Option<Piece>,
+    /// Move was castle.
+    pub castle: bool,
+    /// Move was pawn taking en passant.
+    pub en_passant: bool,
+    /// New [PieceType] of promoted pawn.
+    pub promotion: Option<PieceType>,
+    /// Opponent is in check after move.
+    pub check: bool,
+}
+
+impl Board {
+    /// Make move only if it is legal move of side to move and report what happened.
+    /// On [Err] ([ChessError::NoPieceOnSquare], [ChessError::WrongSideToMove] or
+    /// [ChessError::IllegalMove]) the [Board] isn't changed.
+    /// Use [Board::make_move] for trusted moves (e.g. generated by [Board::gen_legal_moves]).
+    pub fn try_make_move(&mut self, mv: ChessMove) -> Result<MoveOutcome, ChessError> {
+        match *self.get(mv.start) {
+            None => return Err(ChessError::NoPieceOnSquare(mv.start)),
+            Some(piece) if piece.color != self.turn => return Err(ChessError::WrongSideToMove),
+            _ => {}
+        }
+        if !self.legal_moves_from(mv.start).contains(&mv) {
+            return Err(ChessError::IllegalMove(mv));
+        }
+
+        let info = self.move_info(mv).unwrap();
+        self.make_move(mv);
+
+        Ok(MoveOutcome {
+            captured: info.captured,
+            castle: info.is_castle(),
+            en_passant: info.kind == MoveKind::EnPassant,
+            promotion: mv.promo,
+            check: self.is_check(self.turn),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Color;
+    use crate::Square;
+
+    #[test]
+    fn outcome_of_moves() {
+        let mut board = Board::from_fen("r3k2r/8/8/8/3pP3/8/8/R3K2R b KQkq e3 0 1").unwrap();
+
+        let outcome = board.try_make_move("d4e3".parse().unwrap()).unwrap();
+        assert!(outcome.en_passant);
+        assert_eq!(
+            outcome.captured,
+            Some(Piece::new(PieceType::Pawn, Color::White))
+        );
+
+        let outcome = board.try_make_move("e1g1".parse().unwrap()).unwrap();
+        assert!(outcome.castle && !outcome.check);
+
+        assert_eq!(
+            board.try_make_move("e3e2".parse().unwrap()),
+            Ok(MoveOutcome {
+                captured: None,
+                castle: false,
+                en_passant: false,
+                promotion: None,
+                check: false,
+            })
+        );
+        assert_eq!(
+            board.try_make_move("b1b8".parse().unwrap()),
+            Err(ChessError::NoPieceOnSquare(Square::B1))
+        );
+        assert_eq!(
+            board.try_make_move("f1f8".parse().unwrap()),
+            Ok(MoveOutcome {
+                captured: None,
+                castle: false,
+                en_passant: false,
+                promotion: None,
+                check: true,
+            })
+        );
+        assert_eq!(
+            board.try_make_move("e8d8".parse().unwrap()),
+            Err(ChessError::IllegalMove("e8d8".parse().unwrap()))
+        );
+        assert_eq!(
+            board.try_make_move("g1g2".parse().unwrap()),
+            Err(ChessError::WrongSideToMove)
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Perft (performance test), counts all legal move sequences of given depth.
+//! Used for testing correctness of move generation.
+
+use crate::Board;
+use crate::ChessMove;
+
+impl Board {
+    /// Count all legal move sequences of given depth from current position.
+    pub fn perft(&self, depth: u32) -> u64 {
+        self.clone().perft_inner(depth)
+    }
+
+    /// Count all legal move sequences of given depth for every legal move separately.
+    /// Useful for finding which move is generated wrong.
+    pub fn perft_divide(&self, depth: u32) -> Vec<(ChessMove, u64)> {
+        let mut board = self.clone();
+        let mut divide = Vec::new();
+
+        if depth == 0 {
+            return divide;
+        }
+
+        for mv in board.gen_legal_moves() {
+            board.make_move(mv);
+            divide.push((mv, board.perft_inner(depth - 1)));
+            board.unmake_move();
+        }
+
+        divide
+    }
+
+    /// Recursive part of perft, uses make and unmake instead of cloning.
+    fn perft_inner(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves = self.gen_legal_moves();
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes = 0;
+        for mv in moves {
+            self.make_move(mv);
+            nodes += self.perft_inner(depth - 1);
+            self.unmake_move();
+        }
+
+        nodes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check perft of position from FEN for depths 1, 2, ...
+    fn check(fen: &str, expected: &[u64]) {
+        let board = Board::from_fen(fen).unwrap();
+        for (depth, nodes) in expected.iter().enumerate() {
+            assert_eq!(
+                board.perft(depth as u32 + 1),
+                *nodes,
+                "perft({}) of {}",
+                depth + 1,
+                fen
+            );
+        }
+    }
+
+    #[test]
+    fn divide() {
+        let board = Board::deafult();
+        let divide = board.perft_divide(2);
+        assert_eq!(divide.len(), 20);
+        assert_eq!(divide.iter().map(|(_, n)| n).sum::<u64>(), 400);
+        let moves: Vec<ChessMove> = divide.iter().map(|(mv, _)| *mv).collect();
+        assert_eq!(moves, board.gen_legal_moves());
+        assert!(divide.iter().all(|(_, n)| *n == 20));
+
+        let board =
+            Board::from_fen("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1")
+                .unwrap();
+        let divide = board.perft_divide(3);
+        assert_eq!(divide.len(), 48);
+        assert_eq!(divide.iter().map(|(_, n)| n).sum::<u64>(), board.perft(3));
+        assert!(board.perft_divide(0).is_empty());
+    }
+
+    #[test]
+    fn start_position() {
+        check(crate::STARTING_FEN, &[20, 400, 8902, 197281]);
+    }
+
+    #[test]
+    fn kiwipete() {
+        check(
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            &[48, 2039, 97862, 4085603],
+        );
+    }
+
+    #[test]
+    fn castling() {
+        check(
+            "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1",
+            &[26, 568, 13744, 314346],
+        );
+    }
+
+    #[test]
+    fn position_3() {
+        check(
+            "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+            &[14, 191, 2812, 43238, 674624],
+        );
+    }
+
+    #[test]
+    fn position_4() {
+        check(
+            "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+            &[6, 264, 9467, 422333],
+        );
+    }
+
+    #[test]
+    fn position_4_mirrored() {
+        check(
+            "r2q1rk1/pP1p2pp/Q4n2/bbp1p3/Np6/1B3NBn/pPPP1PPP/R3K2R b KQ - 0 1",
+            &[6, 264, 9467, 422333],
+        );
+    }
+
+    #[test]
+    fn position_5() {
+        check(
+            "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+            &[44, 1486, 62379, 2103487],
+        );
+    }
+
+    #[test]
+    fn position_6() {
+        check(
+            "r4rk1/1pp1qppp/p1np1n2/2b1p1B1/2B1P1b1/P1NP1N2/1PP1QPPP/R4RK1 w - - 0 10",
+            &[46, 2079, 89890, 3894594],
+        );
+    }
+
+    #[test]
+    fn chess960() {
+        check(
+            "bqnb1rkr/pp3ppp/3ppn2/2p5/5P2/P2P4/NPP1P1PP/BQ1BNRKR w HFhf - 2 9",
+            &[21, 528, 12189, 326672],
+        );
+        check(
+            "2nnrbkr/p1qppppp/8/1ppb4/6PP/3PP3/PPP2P2/BQNNRBKR w HEhe - 1 9",
+            &[21, 807, 18002, 667366],
+        );
+        check(
+            "b1q1rrkb/pppppppp/3nn3/8/P7/1PPP4/4PPPP/BQNNRKRB w GE - 1 9",
+            &[20, 479, 10471, 273318],
+        );
+    }
+}
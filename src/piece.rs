@@ -1,3 +1,4 @@
+use crate::ChessError;
 use crate::ChessMove;
 use crate::Color;
 use crate::Square;
@@ -13,6 +14,46 @@ pub enum PieceType {
     King,
 }
 
+impl PieceType {
+    /// Function for converting [char] to [PieceType] (case insensitive, e.g. 'n' or 'N').
+    /// If conversion fails [ChessError] is returned.
+    pub(crate) fn try_from_char(c: char) -> Result<Self, ChessError> {
+        match c.to_ascii_lowercase() {
+            'p' => Ok(PieceType::Pawn),
+            'n' => Ok(PieceType::Knight),
+            'b' => Ok(PieceType::Bishop),
+            'r' => Ok(PieceType::Rook),
+            'q' => Ok(PieceType::Queen),
+            'k' => Ok(PieceType::King),
+            _ => Err(ChessError::InvalidNotation),
+        }
+    }
+
+    /// Converts [PieceType] to lowercase [char].
+    pub fn to_char(self) -> char {
+        match self {
+            PieceType::Pawn => 'p',
+            PieceType::Knight => 'n',
+            PieceType::Bishop => 'b',
+            PieceType::Rook => 'r',
+            PieceType::Queen => 'q',
+            PieceType::King => 'k',
+        }
+    }
+
+    /// Material value of [PieceType] in centipawns (king has no value).
+    pub fn value(self) -> i32 {
+        match self {
+            PieceType::Pawn => 100,
+            PieceType::Knight => 300,
+            PieceType::Bishop => 320,
+            PieceType::Rook => 500,
+            PieceType::Queen => 900,
+            PieceType::King => 0,
+        }
+    }
+}
+
 /// Represent a piece with type and color.
 /// Doesn't hold any information about their position on the [crate::Board].
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -26,4 +67,40 @@ impl Piece {
     pub fn new(piece_type: PieceType, color: Color) -> Piece {
         Piece { piece_type, color }
     }
+
+    /// Function for converting FEN [char] to [Piece] (uppercase is white, lowercase is black).
+    /// If conversion fails [ChessError] is returned.
+    pub(crate) fn try_from_char(c: char) -> Result<Self, ChessError> {
+        let color = if c.is_ascii_uppercase() {
+            Color::White
+        } else {
+            Color::Black
+        };
+        Ok(Piece::new(PieceType::try_from_char(c)?, color))
+    }
+
+    /// Converts [Piece] to FEN [char] (uppercase is white, lowercase is black).
+    pub fn to_char(self) -> char {
+        match self.color {
+            Color::White => self.piece_type.to_char().to_ascii_uppercase(),
+            Color::Black => self.piece_type.to_char(),
+        }
+    }
+
+    /// Converts [Piece] to Unicode chess figurine (e.g. '♔' for white king).
+    pub fn to_unicode(self) -> char {
+        let white = match self.piece_type {
+            PieceType::Pawn => '♙',
+            PieceType::Knight => '♘',
+            PieceType::Bishop => '♗',
+            PieceType::Rook => '♖',
+            PieceType::Queen => '♕',
+            PieceType::King => '♔',
+        };
+        match self.color {
+            Color::White => white,
+            // black figurines are 6 code points after white ones
+            Color::Black => char::from_u32(white as u32 + 6).unwrap(),
+        }
+    }
 }
@@ -1,3 +1,5 @@
+use crate::ChessError;
+
 /// Rank enum for simple orientation on the [crate::Board].
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Rank {
@@ -13,8 +15,8 @@ pub enum Rank {
 
 impl Rank {
     /// Function for converting [usize] to [Rank].
-    /// If conversion fails error message is returned in form of [str].
-    pub(crate) fn try_from_usize(num: usize) -> Result<Self, &'static str> {
+    /// If conversion fails [ChessError] is returned.
+    pub(crate) fn try_from_usize(num: usize) -> Result<Self, ChessError> {
         match num {
             0 => Ok(Self::Eighth),
             1 => Ok(Self::Seventh),
@@ -24,12 +26,21 @@ impl Rank {
             5 => Ok(Self::Third),
             6 => Ok(Self::Second),
             7 => Ok(Self::First),
-            _ => Err("Rank::try_from_usize endup with Error: usize to big."),
+            _ => Err(ChessError::OutOfBounds),
+        }
+    }
+
+    /// Function for converting [char] ('1'..='8') to [Rank].
+    /// If conversion fails [ChessError] is returned.
+    pub(crate) fn try_from_char(c: char) -> Result<Self, ChessError> {
+        match c {
+            '1'..='8' => Rank::try_from_usize('8' as usize - c as usize),
+            _ => Err(ChessError::InvalidNotation),
         }
     }
 
     /// Converts [Rank] to [usize].
-    pub(crate) fn to_usize(&self) -> usize {
+    pub(crate) fn to_usize(self) -> usize {
         match self {
             Self::First => 7,
             Self::Second => 6,
@@ -42,17 +53,14 @@ impl Rank {
         }
     }
 
-    /// Converts [Rank] to [String].
-    pub fn to_string(&self) -> String {
-        match self {
-            Self::First => 1.to_string(),
-            Self::Second => 2.to_string(),
-            Self::Third => 3.to_string(),
-            Self::Fourth => 4.to_string(),
-            Self::Fifth => 5.to_string(),
-            Self::Sixth => 6.to_string(),
-            Self::Seventh => 7.to_string(),
-            Self::Eighth => 8.to_string(),
-        }
+    /// Converts [Rank] to [char] ('1'..='8').
+    pub fn to_char(self) -> char {
+        (b'8' - self.to_usize() as u8) as char
+    }
+}
+
+impl std::fmt::Display for Rank {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
     }
 }
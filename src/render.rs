@@ -0,0 +1,150 @@
+//! Text rendering of [Board] with [BoardRenderer], also used by [std::fmt::Display] of [Board].
+
+use crate::Board;
+use crate::Color;
+use crate::PieceType;
+use crate::Square;
+
+/// Options of text rendering of [Board].
+/// Squares of last move are rendered in brackets `[P]`, king in check in parentheses `(K)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoardRenderer {
+    /// Use Unicode figurines (e.g. '♔') instead of FEN letters.
+    pub unicode: bool,
+    /// Show rank and file labels.
+    pub labels: bool,
+    /// Render board from black's perspective.
+    pub flipped: bool,
+    /// Mark start and destination square of last move.
+    pub highlight_last_move: bool,
+    /// Mark square of king in check.
+    pub highlight_check: bool,
+}
+
+impl Default for BoardRenderer {
+    fn default() -> Self {
+        BoardRenderer {
+            unicode: false,
+            labels: true,
+            flipped: false,
+            highlight_last_move: true,
+            highlight_check: true,
+        }
+    }
+}
+
+impl BoardRenderer {
+    /// Render [Board] to multiline [String], every rank on its own line.
+    pub fn render(&self, board: &Board) -> String {
+        let last_move = board.last_move().filter(|_| self.highlight_last_move);
+        let checked: Vec<Square> = [Color::White, Color::Black]
+            .into_iter()
+            .filter(|&color| self.highlight_check && board.is_check(color))
+            .filter_map(|color| (board.pieces(PieceType::King) & board.color_pieces(color)).first())
+            .collect();
+
+        let order: Vec<usize> = if self.flipped {
+            (0..8).rev().collect()
+        } else {
+            (0..8).collect()
+        };
+
+        let mut text = String::new();
+        for &row in &order {
+            let mut line = String::new();
+            if self.labels {
+                line.push((b'8' - row as u8) as char);
+                line.push(' ');
+            }
+            for &col in &order {
+                let sq = Square(row * 8 + col);
+                let (open, close) = if checked.contains(&sq) {
+                    ('(', ')')
+                } else if last_move.is_some_and(|mv| mv.start == sq || mv.dest == sq) {
+                    ('[', ']')
+                } else {
+                    (' ', ' ')
+                };
+                line.push(open);
+                line.push(match board.get(sq) {
+                    Some(piece) if self.unicode => piece.to_unicode(),
+                    Some(piece) => piece.to_char(),
+                    None if self.unicode => '·',
+                    None => '.',
+                });
+                line.push(close);
+            }
+            text.push_str(line.trim_end());
+            text.push('\n');
+        }
+
+        if self.labels {
+            text.push_str("  ");
+            for &col in &order {
+                text.push(' ');
+                text.push((b'a' + col as u8) as char);
+                text.push(' ');
+            }
+            text.truncate(text.trim_end().len());
+            text.push('\n');
+        }
+
+        text
+    }
+}
+
+/// Render [Board] with deafult [BoardRenderer], alternate flag (`{:#}`) uses Unicode figurines.
+impl std::fmt::Display for Board {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let renderer = BoardRenderer {
+            unicode: f.alternate(),
+            ..BoardRenderer::default()
+        };
+        write!(f, "{}", renderer.render(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_board() {
+        let mut board = Board::deafult();
+        assert_eq!(
+            board.to_string(),
+            "8  r  n  b  q  k  b  n  r
+7  p  p  p  p  p  p  p  p
+6  .  .  .  .  .  .  .  .
+5  .  .  .  .  .  .  .  .
+4  .  .  .  .  .  .  .  .
+3  .  .  .  .  .  .  .  .
+2  P  P  P  P  P  P  P  P
+1  R  N  B  Q  K  B  N  R
+   a  b  c  d  e  f  g  h
+"
+        );
+
+        for mv in ["e2e4", "f7f6", "d1h5"] {
+            board.make_move(mv.parse().unwrap());
+        }
+        let renderer = BoardRenderer {
+            unicode: true,
+            flipped: true,
+            ..BoardRenderer::default()
+        };
+        assert_eq!(
+            renderer.render(&board),
+            "1  ♖  ♘  ♗  ♔ [·] ♗  ♘  ♖
+2  ♙  ♙  ♙  ·  ♙  ♙  ♙  ♙
+3  ·  ·  ·  ·  ·  ·  ·  ·
+4  ·  ·  ·  ♙  ·  ·  ·  ·
+5 [♕] ·  ·  ·  ·  ·  ·  ·
+6  ·  ·  ♟  ·  ·  ·  ·  ·
+7  ♟  ♟  ·  ♟  ♟  ♟  ♟  ♟
+8  ♜  ♞  ♝ (♚) ♛  ♝  ♞  ♜
+   h  g  f  e  d  c  b  a
+"
+        );
+    }
+}
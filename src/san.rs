@@ -0,0 +1,330 @@
+//! Standard Algebraic Notation (SAN) of [ChessMove], e.g. "Nbd2", "exd5", "O-O" or "e8=Q+".
+
+use crate::Board;
+use crate::ChessMove;
+use crate::File;
+use crate::GameStatus;
+use crate::MoveKind;
+use crate::PieceType;
+use crate::Rank;
+use crate::Square;
+
+/// Errors which can occur while parsing SAN with [ChessMove::from_san].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SanError {
+    /// String isn't valid SAN.
+    InvalidSan,
+    /// SAN is valid, but there is no such legal move on the [Board].
+    IllegalMove,
+    /// SAN matches more than one legal move (missing disambiguation).
+    AmbiguousMove,
+}
+
+impl std::fmt::Display for SanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            SanError::InvalidSan => "invalid SAN",
+            SanError::IllegalMove => "illegal move",
+            SanError::AmbiguousMove => "ambiguous move",
+        };
+        write!(f, "SAN error: {}.", msg)
+    }
+}
+
+impl std::error::Error for SanError {}
+
+impl ChessMove {
+    /// Convert [ChessMove] to SAN, return [SanError::IllegalMove] if it isn't legal move
+    /// of side to move on the [Board].
+    pub fn to_san(&self, board: &Board) -> Result<String, SanError> {
+        if !board.legal_moves_from(self.start).contains(self) {
+            return Err(SanError::IllegalMove);
+        }
+        // legal move always has piece on start square
+        let info = board.move_info(*self).unwrap();
+        let piece = info.piece;
+
+        let mut san = String::new();
+
+        if info.is_castle() {
+            if info.kind == MoveKind::CastleKingside {
+                san.push_str("O-O");
+            } else {
+                san.push_str("O-O-O");
+            }
+        } else if piece.piece_type == PieceType::Pawn {
+            if info.is_capture() {
+                san.push(self.start.get_file().unwrap().to_char());
+                san.push('x');
+            }
+            san.push_str(&self.dest.to_string());
+            if let Some(promo) = self.promo {
+                san.push('=');
+                san.push(promo.to_char().to_ascii_uppercase());
+            }
+        } else {
+            san.push(piece.piece_type.to_char().to_ascii_uppercase());
+
+            // disambiguation, other pieces of same type which can go to same square
+            let others: Vec<Square> = board
+                .legal_moves_to(self.dest)
+                .into_iter()
+                .filter(|mv| mv.start != self.start && *board.get(mv.start) == Some(piece))
+                .map(|mv| mv.start)
+                .collect();
+            if !others.is_empty() {
+                let same_file = others
+                    .iter()
+                    .any(|sq| sq.get_file() == self.start.get_file());
+                let same_rank = others
+                    .iter()
+                    .any(|sq| sq.get_rank() == self.start.get_rank());
+                if !same_file {
+                    san.push(self.start.get_file().unwrap().to_char());
+                } else if !same_rank {
+                    san.push(self.start.get_rank().unwrap().to_char());
+                } else {
+                    san.push_str(&self.start.to_string());
+                }
+            }
+
+            if info.is_capture() {
+                san.push('x');
+            }
+            san.push_str(&self.dest.to_string());
+        }
+
+        // check and checkmate
+        let mut after = board.clone();
+        after.make_move(*self);
+        if let GameStatus::Checkmate(_) = after.status() {
+            san.push('#');
+        } else if after.is_check(piece.color.get_inverse()) {
+            san.push('+');
+        }
+
+        Ok(san)
+    }
+
+    /// Parse SAN to legal [ChessMove] of side to move on the [Board].
+    /// Check, checkmate and annotation suffixes ("+", "#", "!", "?") are ignored.
+    /// Coordinate notation (e.g. "e2e4" or "b2c3") isn't SAN and is refused.
+    pub fn from_san(san: &str, board: &Board) -> Result<ChessMove, SanError> {
+        let san = san.trim().trim_end_matches(['+', '#', '!', '?']);
+        let legal = board.gen_legal_moves();
+
+        let castle = match san {
+            "O-O" | "0-0" => Some(MoveKind::CastleKingside),
+            "O-O-O" | "0-0-0" => Some(MoveKind::CastleQueenside),
+            _ => None,
+        };
+        if let Some(kind) = castle {
+            return legal
+                .into_iter()
+                .find(|mv| board.move_info(*mv).is_some_and(|info| info.kind == kind))
+                .ok_or(SanError::IllegalMove);
+        }
+
+        let mut chars: Vec<char> = san.chars().collect();
+
+        // promotion, e.g. "e8=Q" or "e8Q"
+        let mut promo = None;
+        if let Some(&last) = chars.last() {
+            if matches!(last, 'N' | 'B' | 'R' | 'Q') {
+                promo = Some(PieceType::try_from_char(last).unwrap());
+                chars.pop();
+                if chars.last() == Some(&'=') {
+                    chars.pop();
+                }
+            }
+        }
+
+        // destination
+        if chars.len() < 2 {
+            return Err(SanError::InvalidSan);
+        }
+        let dest: Square = chars
+            .split_off(chars.len() - 2)
+            .into_iter()
+            .collect::<String>()
+            .parse()
+            .map_err(|_| SanError::InvalidSan)?;
+
+        // piece type
+        let piece_type = match chars.first() {
+            Some(c @ ('N' | 'B' | 'R' | 'Q' | 'K')) => {
+                let piece_type = PieceType::try_from_char(*c).unwrap();
+                chars.remove(0);
+                piece_type
+            }
+            _ => PieceType::Pawn,
+        };
+
+        let capture = chars.last() == Some(&'x');
+        if capture {
+            chars.pop();
+        }
+
+        // disambiguation, file and/or rank of start square
+        let mut from_file = None;
+        let mut from_rank = None;
+        for c in chars {
+            if let Ok(file) = File::try_from_char(c) {
+                from_file = Some(file);
+            } else if let Ok(rank) = Rank::try_from_char(c) {
+                from_rank = Some(rank);
+            } else {
+                return Err(SanError::InvalidSan);
+            }
+        }
+
+        // pawn capture has only file of start square ("exd5"), pawn push nothing ("e4")
+        if piece_type == PieceType::Pawn && (from_rank.is_some() || from_file.is_some() != capture)
+        {
+            return Err(SanError::InvalidSan);
+        }
+
+        let mut candidates = legal.into_iter().filter(|mv| {
+            mv.dest == dest
+                && matches!(board.get(mv.start), Some(p) if p.piece_type == piece_type)
+                && from_file.is_none_or(|file| mv.start.get_file() == Ok(file))
+                && from_rank.is_none_or(|rank| mv.start.get_rank() == Ok(rank))
+                && mv.promo == promo
+                && !is_castle(board, mv)
+        });
+
+        match (candidates.next(), candidates.next()) {
+            (Some(mv), None) => Ok(mv),
+            (Some(_), Some(_)) => Err(SanError::AmbiguousMove),
+            (None, _) => Err(SanError::IllegalMove),
+        }
+    }
+}
+
+/// Check if legal [ChessMove] is castle.
+fn is_castle(board: &Board, mv: &ChessMove) -> bool {
+    board.move_info(*mv).is_some_and(|info| info.is_castle())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Check that [ChessMove] in UCI is written as `san` and `san` is parsed back.
+    fn check(fen: &str, uci: &str, san: &str) {
+        let board = Board::from_fen(fen).unwrap();
+        let mv: ChessMove = uci.parse().unwrap();
+        assert_eq!(mv.to_san(&board).as_deref(), Ok(san), "{} in {}", uci, fen);
+        assert_eq!(
+            ChessMove::from_san(san, &board),
+            Ok(mv),
+            "{} in {}",
+            san,
+            fen
+        );
+    }
+
+    #[test]
+    fn disambiguation() {
+        let fen = "r1bqkbnr/pppppppp/8/8/8/5N2/PPP1PPPP/RNBQKB1R w KQkq - 0 1";
+        check(fen, "b1d2", "Nbd2");
+        check(fen, "f3d2", "Nfd2");
+        check(fen, "b1c3", "Nc3");
+        check("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a1a3", "R1a3");
+        check("4k3/8/8/R7/8/8/8/R3K3 w - - 0 1", "a5a3", "R5a3");
+        let fen = "1k6/8/8/8/4Q2Q/8/8/K6Q w - - 0 1";
+        check(fen, "h4e1", "Qh4e1");
+        check(fen, "e4e1", "Qee1");
+        check(fen, "h1e1", "Q1e1");
+    }
+
+    #[test]
+    fn captures() {
+        check("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6", "exd6");
+        // lowercase file is pawn, uppercase is bishop
+        let fen = "4k3/8/8/8/8/2n5/1P1B4/4K3 w - - 0 1";
+        check(fen, "b2c3", "bxc3");
+        check(fen, "d2c3", "Bxc3");
+    }
+
+    #[test]
+    fn castles() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        check(fen, "e1g1", "O-O");
+        check(fen, "e1c1", "O-O-O");
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(
+            ChessMove::from_san("0-0", &board),
+            Ok("e1g1".parse().unwrap())
+        );
+
+        // Chess960, castles are encoded as king takes own rook
+        let fen = "1r2k1r1/pppppppp/8/8/8/8/PPPPPPPP/RK4R1 w GAgb - 0 1";
+        check(fen, "b1g1", "O-O");
+        check(fen, "b1a1", "O-O-O");
+    }
+
+    #[test]
+    fn promotions_and_checks() {
+        let fen = "4k3/P7/8/8/8/8/8/4K3 w - - 0 1";
+        check(fen, "a7a8q", "a8=Q+");
+        check(fen, "a7a8n", "a8=N");
+        let board = Board::from_fen(fen).unwrap();
+        assert_eq!(
+            ChessMove::from_san("a8Q", &board),
+            Ok("a7a8q".parse().unwrap())
+        );
+        assert_eq!(
+            ChessMove::from_san("a8", &board),
+            Err(SanError::IllegalMove)
+        );
+
+        let fen = "r1bqkb1r/pppp1ppp/2n2n2/4p2Q/2B1P3/8/PPPP1PPP/RNB1K1NR w KQkq - 4 4";
+        check(fen, "h5f7", "Qxf7#");
+        let board = Board::from_fen(fen).unwrap();
+        for san in ["Qxf7", "Qxf7+", "Qxf7+!?"] {
+            assert_eq!(
+                ChessMove::from_san(san, &board),
+                Ok("h5f7".parse().unwrap())
+            );
+        }
+    }
+
+    #[test]
+    fn illegal_to_san() {
+        let board = Board::deafult();
+        for uci in ["e2e5", "e7e5", "e1e2", "a1a3", "d1h5"] {
+            let mv: ChessMove = uci.parse().unwrap();
+            assert_eq!(mv.to_san(&board), Err(SanError::IllegalMove), "{}", uci);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let board =
+            Board::from_fen("r1bqkbnr/pppppppp/8/8/8/5N2/PPP1PPPP/RNBQKB1R w KQkq - 0 1").unwrap();
+        assert_eq!(
+            ChessMove::from_san("Nd2", &board),
+            Err(SanError::AmbiguousMove)
+        );
+        assert_eq!(
+            ChessMove::from_san("Nc4", &board),
+            Err(SanError::IllegalMove)
+        );
+        assert_eq!(
+            ChessMove::from_san("O-O", &board),
+            Err(SanError::IllegalMove)
+        );
+        // coordinates and incomplete pawn captures aren't SAN
+        for san in [
+            "", "e", "Xe4", "e9", "Nzd2", "e2e4", "b2c3", "bc3", "xc3", "b2xc3",
+        ] {
+            assert_eq!(
+                ChessMove::from_san(san, &board),
+                Err(SanError::InvalidSan),
+                "{}",
+                san
+            );
+        }
+    }
+}
@@ -1,13 +1,12 @@
 /// Allow unused import crate::Board for using [Board] in documentation.
 #[allow(unused_imports)]
 use crate::Board;
-use crate::ChessMove;
+use crate::ChessError;
 use crate::File;
-use crate::PieceType;
 use crate::Rank;
 
 /// Represent a index on the [Board].
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Square(pub/*(crate)*/ usize);
 
 impl Square {
@@ -19,139 +18,16 @@ impl Square {
 
     /// Extract [Rank] from [Square], return [Result].
     /// Return [Err] when [Square] is not valid (it's outside the [Board]).
-    pub fn get_rank(&self) -> Result<Rank, &str> {
+    pub fn get_rank(&self) -> Result<Rank, ChessError> {
         Rank::try_from_usize(self.0 / 8)
     }
 
     /// Extract [File] from [Square], return [Result].
     /// Return [Err] when [Square] is not valid (it's outside the [Board]).
-    pub fn get_file(&self) -> Result<File, &str> {
+    pub fn get_file(&self) -> Result<File, ChessError> {
         File::try_from_usize(self.0 % 8)
     }
 
-    const CREATE_MOVE_ERR: Result<ChessMove, &'static str> =
-        Err("The resulting square is out of bounds.");
-
-    /// Returning [Result].
-    /// Create a new instance of [ChessMove] from [Square] destination that is moved n squares up.
-    /// Return [Err] if [Square] is out of bounds of board.
-    fn create_move(
-        &self,
-        sq_i: usize,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        let sq = Square(sq_i);
-        // outside: up/down
-        // outside: left/right is handled by functions which creating that moves
-        if sq.0 > 63 {
-            return Square::CREATE_MOVE_ERR;
-        }
-
-        Ok(ChessMove::new(*self, sq, promo))
-    }
-
-    /// Simplifie the procces of creating diagonal move.
-    /// Centralize the error catching, which is same for every diagonal move.
-    fn create_diagonal_move(
-        &self,
-        sq_i: usize,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        let sq = Square(sq_i);
-        if sq.0 > 63
-            || (self.get_rank().unwrap().to_usize() as i32
-                - sq.get_rank().unwrap().to_usize() as i32)
-                .abs()
-                != mul
-        {
-            return Square::CREATE_MOVE_ERR;
-        }
-        self.create_move(sq.0, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares down.
-    pub(crate) fn down(
-        &self,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        self.create_move((self.0 as i32 + 8 * mul) as usize, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares up.
-    pub(crate) fn up(&self, mul: i32, promo: Option<PieceType>) -> Result<ChessMove, &'static str> {
-        self.create_move((self.0 as i32 - 8 * mul) as usize, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares left.
-    pub(crate) fn left(
-        &self,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        let sq = Square((self.0 as i32 + mul) as usize);
-        if self.0 > 63 || sq.0 > 63 {
-            return Square::CREATE_MOVE_ERR;
-        }
-        if self.get_rank().unwrap() != sq.get_rank().unwrap() {
-            return Square::CREATE_MOVE_ERR;
-        }
-        self.create_move(sq.0, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares right.
-    pub(crate) fn right(
-        &self,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        let sq = Square((self.0 as i32 - mul) as usize);
-        if self.0 > 63 || sq.0 > 63 {
-            return Square::CREATE_MOVE_ERR;
-        }
-        if self.get_rank().unwrap() != sq.get_rank().unwrap() {
-            return Square::CREATE_MOVE_ERR;
-        }
-        self.create_move(sq.0, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares right and down.
-    pub(crate) fn up_left(
-        &self,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        self.create_diagonal_move((self.0 as i32 - 9 * mul) as usize, mul, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares left and down.
-    pub(crate) fn up_right(
-        &self,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        self.create_diagonal_move((self.0 as i32 - 7 * mul) as usize, mul, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares right and up.
-    pub(crate) fn down_left(
-        &self,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        self.create_diagonal_move((self.0 as i32 + 9 * mul) as usize, mul, promo)
-    }
-
-    /// Create [ChessMove] from [Square] to n squares left and up.
-    pub(crate) fn down_right(
-        &self,
-        mul: i32,
-        promo: Option<PieceType>,
-    ) -> Result<ChessMove, &'static str> {
-        self.create_diagonal_move((self.0 as i32 + 7 * mul) as usize, mul, promo)
-    }
-
     /// built in constant A1 [Square] for easy indexing to [Board]
     pub const A1: Square = Square(56);
     /// built in constant B1 [Square] for easy indexing to [Board]
@@ -281,3 +157,29 @@ impl Square {
     /// built in constant H8 [Square] for easy indexing to [Board]
     pub const H8: Square = Square(7);
 }
+
+impl std::fmt::Display for Square {
+    /// Format [Square] in algebraic notation (e.g. "e4").
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.get_file(), self.get_rank()) {
+            (Ok(file), Ok(rank)) => write!(f, "{}{}", file, rank),
+            _ => Err(std::fmt::Error),
+        }
+    }
+}
+
+impl std::str::FromStr for Square {
+    type Err = ChessError;
+
+    /// Parse [Square] from algebraic notation (e.g. "e4").
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(file), Some(rank), None) => Ok(Square::new(
+                Rank::try_from_char(rank)?,
+                File::try_from_char(file)?,
+            )),
+            _ => Err(ChessError::InvalidNotation),
+        }
+    }
+}
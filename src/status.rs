@@ -0,0 +1,220 @@
+//! Detection of check and end of the game.
+
+use crate::Board;
+use crate::Color;
+use crate::PieceType;
+
+/// Reason why game ended in draw.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DrawReason {
+    /// Neither side can checkmate (K vs K, K+B vs K, K+N vs K or only bishops on same color).
+    InsufficientMaterial,
+    /// No capture or pawn move in last fifty moves of each side.
+    FiftyMoveRule,
+    /// Same position occurred three times (with same castling rights, side to move and en passant).
+    ThreefoldRepetition,
+}
+
+/// State of the game on the [Board].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GameStatus {
+    /// Side to move has at least one legal move.
+    Ongoing,
+    /// Game ended by checkmate, [Color] of the winner.
+    Checkmate(Color),
+    /// Side to move has no legal move, but isn't in check.
+    Stalemate,
+    /// Game ended in draw.
+    Draw(DrawReason),
+}
+
+impl Board {
+    /// Check if king of [Color] is attacked.
+    /// Return false if there is no king of that [Color] on the [Board].
+    pub fn is_check(&self, color: Color) -> bool {
+        (self.pieces(PieceType::King) & self.color_pieces(color))
+            .iter()
+            .any(|sq| self.is_square_attacked(sq, color.get_inverse()))
+    }
+
+    /// Decide [GameStatus] of the [Board] from the view of side to move.
+    pub fn status(&self) -> GameStatus {
+        if self.gen_legal_moves().is_empty() {
+            if self.is_check(self.turn) {
+                GameStatus::Checkmate(self.turn.get_inverse())
+            } else {
+                GameStatus::Stalemate
+            }
+        } else if self.is_insufficient_material() {
+            GameStatus::Draw(DrawReason::InsufficientMaterial)
+        } else if self.halfmove_clock >= 100 {
+            GameStatus::Draw(DrawReason::FiftyMoveRule)
+        } else if self.repetition_count() >= 3 {
+            GameStatus::Draw(DrawReason::ThreefoldRepetition)
+        } else {
+            GameStatus::Ongoing
+        }
+    }
+
+    /// Count how many times current position occurred in the game (including current one).
+    pub fn repetition_count(&self) -> usize {
+        let key = self.hash;
+        // position can't repeat before last capture or pawn move
+        let since = self
+            .key_history
+            .len()
+            .saturating_sub(self.halfmove_clock as usize);
+
+        self.key_history[since..]
+            .iter()
+            .filter(|k| **k == key)
+            .count()
+            + 1
+    }
+
+    /// Check if none of sides has enough material to checkmate.
+    pub fn is_insufficient_material(&self) -> bool {
+        let mut minors = 0;
+        // square colors of bishops, index 0 is light and 1 is dark
+        let mut bishops = [false; 2];
+
+        for (i, piece) in self.iter().enumerate() {
+            if let Some(piece) = piece {
+                match piece.piece_type {
+                    PieceType::King => {}
+                    PieceType::Knight => minors += 1,
+                    PieceType::Bishop => bishops[(i / 8 + i % 8) % 2] = true,
+                    _ => return false,
+                }
+            }
+        }
+
+        match (minors, bishops) {
+            // only kings, or kings with bishops on same color
+            (0, [true, true]) => false,
+            (0, _) => true,
+            // single knight
+            (1, [false, false]) => true,
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Make moves in UCI on the [Board].
+    fn play(board: &mut Board, moves: &str) {
+        for mv in moves.split_whitespace() {
+            board.make_move(mv.parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn check_mate_and_stalemate() {
+        let board = Board::deafult();
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        assert!(!board.is_check(Color::White) && !board.is_check(Color::Black));
+
+        // stalemate, black king has no move and isn't in check
+        let board = Board::from_fen("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1").unwrap();
+        assert!(!board.is_check(Color::Black));
+        assert_eq!(board.status(), GameStatus::Stalemate);
+
+        // fool's mate
+        let board =
+            Board::from_fen("rnb1kbnr/pppp1ppp/8/4p3/6Pq/5P2/PPPPP2P/RNBQKBNR w KQkq - 1 3")
+                .unwrap();
+        assert!(board.is_check(Color::White));
+        assert!(!board.is_check(Color::Black));
+        assert_eq!(board.status(), GameStatus::Checkmate(Color::Black));
+
+        // check, but king can escape
+        let board = Board::from_fen("R6k/8/8/8/8/8/8/6K1 b - - 0 1").unwrap();
+        assert!(board.is_check(Color::Black));
+        assert_eq!(board.status(), GameStatus::Ongoing);
+
+        // board without king isn't in check
+        let board = Board::empty(Color::White);
+        assert!(!board.is_check(Color::White));
+    }
+
+    #[test]
+    fn threefold_repetition() {
+        let mut board = Board::deafult();
+        play(&mut board, "g1f3 g8f6 f3g1 f6g8");
+        assert_eq!(board.repetition_count(), 2);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        play(&mut board, "g1f3 g8f6 f3g1 f6g8");
+        assert_eq!(board.repetition_count(), 3);
+        assert_eq!(
+            board.status(),
+            GameStatus::Draw(DrawReason::ThreefoldRepetition)
+        );
+
+        // same pieces, but castling rights are lost after first rook moves
+        let mut board = Board::from_fen("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1").unwrap();
+        play(&mut board, "a1b1 a8b8 b1a1 b8a8 a1b1 a8b8 b1a1 b8a8");
+        assert_eq!(board.repetition_count(), 2);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        play(&mut board, "a1b1 a8b8 b1a1 b8a8");
+        assert_eq!(board.repetition_count(), 3);
+
+        // same pieces, but en passant capture is possible only after double push
+        let mut board = Board::from_fen("4k3/8/8/8/3p4/8/4P3/4K3 w - - 0 1").unwrap();
+        play(&mut board, "e2e4 e8d8 e1d1 d8e8 d1e1 e8d8 e1d1 d8e8 d1e1");
+        assert_eq!(board.repetition_count(), 2);
+        assert_eq!(board.status(), GameStatus::Ongoing);
+    }
+
+    #[test]
+    fn fifty_move_rule() {
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+        assert_eq!(board.status(), GameStatus::Ongoing);
+        let board = Board::from_fen("4k3/8/8/8/8/8/8/R3K3 w - - 100 80").unwrap();
+        assert_eq!(board.status(), GameStatus::Draw(DrawReason::FiftyMoveRule));
+
+        // move reaching 100 half moves is checkmate
+        let mut board = Board::from_fen("6k1/5ppp/8/8/8/8/8/R3K3 w - - 99 80").unwrap();
+        play(&mut board, "a1a8");
+        assert_eq!(board.halfmove_clock(), 100);
+        assert_eq!(board.status(), GameStatus::Checkmate(Color::White));
+    }
+
+    #[test]
+    fn insufficient_material() {
+        for fen in [
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/1N2K3 w - - 0 1",
+            "4kb2/8/8/8/8/8/8/4K3 w - - 0 1",
+            // bishops of both sides on same color
+            "3bk3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/B1B1K3 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert!(board.is_insufficient_material(), "{}", fen);
+            assert_eq!(
+                board.status(),
+                GameStatus::Draw(DrawReason::InsufficientMaterial),
+                "{}",
+                fen
+            );
+        }
+
+        for fen in [
+            // bishops on opposite colors
+            "2b1k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/1NB1K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1",
+            "1n2k3/8/8/8/8/8/8/1N2K3 w - - 0 1",
+            "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/R3K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/3QK3 w - - 0 1",
+        ] {
+            let board = Board::from_fen(fen).unwrap();
+            assert!(!board.is_insufficient_material(), "{}", fen);
+        }
+    }
+}
@@ -0,0 +1,172 @@
+//! UCI long algebraic notation of [ChessMove], e.g. "e2e4" or "e7e8q".
+
+use crate::Board;
+use crate::ChessMove;
+use crate::PieceType;
+use crate::Square;
+
+/// Errors which can occur while parsing UCI move with [ChessMove::from_uci] or [str::parse].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UciError {
+    /// String isn't valid UCI move.
+    InvalidUci,
+    /// UCI move is valid, but it isn't legal move on the [Board].
+    IllegalMove,
+}
+
+impl std::fmt::Display for UciError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let msg = match self {
+            UciError::InvalidUci => "invalid UCI move",
+            UciError::IllegalMove => "illegal move",
+        };
+        write!(f, "UCI error: {}.", msg)
+    }
+}
+
+impl std::error::Error for UciError {}
+
+impl std::fmt::Display for ChessMove {
+    /// Format [ChessMove] in UCI notation (e.g. "e2e4" or "e7e8q").
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.start, self.dest)?;
+        if let Some(promo) = self.promo {
+            write!(f, "{}", promo.to_char())?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for ChessMove {
+    type Err = UciError;
+
+    /// Parse [ChessMove] from UCI notation, doesn't check if move is legal.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if !s.is_ascii() || (s.len() != 4 && s.len() != 5) {
+            return Err(UciError::InvalidUci);
+        }
+
+        let start: Square = s[0..2].parse().map_err(|_| UciError::InvalidUci)?;
+        let dest: Square = s[2..4].parse().map_err(|_| UciError::InvalidUci)?;
+        let promo = match s[4..].chars().next() {
+            Some(c @ ('n' | 'b' | 'r' | 'q')) => Some(PieceType::try_from_char(c).unwrap()),
+            Some(_) => return Err(UciError::InvalidUci),
+            None => None,
+        };
+
+        Ok(ChessMove::new(start, dest, promo))
+    }
+}
+
+impl ChessMove {
+    /// Parse UCI notation to legal [ChessMove] of side to move on the [Board].
+    pub fn from_uci(uci: &str, board: &Board) -> Result<ChessMove, UciError> {
+        let mv: ChessMove = uci.trim().parse()?;
+
+        if board.legal_moves_from(mv.start).contains(&mv) {
+            Ok(mv)
+        } else {
+            Err(UciError::IllegalMove)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_format() {
+        let mv: ChessMove = "e2e4".parse().unwrap();
+        assert_eq!(mv, ChessMove::new(Square::E2, Square::E4, None));
+        assert_eq!(mv.to_string(), "e2e4");
+
+        let mv: ChessMove = "e7e8q".parse().unwrap();
+        assert_eq!(
+            mv,
+            ChessMove::new(Square::E7, Square::E8, Some(PieceType::Queen))
+        );
+        assert_eq!(mv.to_string(), "e7e8q");
+        assert_eq!("a2a1n".parse::<ChessMove>().unwrap().to_string(), "a2a1n");
+
+        for uci in [
+            "", "e2e", "i1a1", "a9a1", "e2e4qq", "e7e8Q", "e7e8k", "e2-e4", "é2e4",
+        ] {
+            assert_eq!(
+                uci.parse::<ChessMove>(),
+                Err(UciError::InvalidUci),
+                "{}",
+                uci
+            );
+        }
+    }
+
+    #[test]
+    fn legal_moves() {
+        let board = Board::from_fen("4k3/P7/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+        assert_eq!(
+            ChessMove::from_uci("a7a8q", &board),
+            Ok("a7a8q".parse().unwrap())
+        );
+        assert_eq!(
+            ChessMove::from_uci(" e2e4\n", &board),
+            Ok("e2e4".parse().unwrap())
+        );
+        // promotion piece is required on last rank and forbidden elsewhere
+        assert_eq!(
+            ChessMove::from_uci("a7a8", &board),
+            Err(UciError::IllegalMove)
+        );
+        assert_eq!(
+            ChessMove::from_uci("e2e4q", &board),
+            Err(UciError::IllegalMove)
+        );
+        assert_eq!(
+            ChessMove::from_uci("a7a8Q", &board),
+            Err(UciError::InvalidUci)
+        );
+        // valid notation, but not legal
+        assert_eq!(
+            ChessMove::from_uci("e2e5", &board),
+            Err(UciError::IllegalMove)
+        );
+        assert_eq!(
+            ChessMove::from_uci("e8e7", &board),
+            Err(UciError::IllegalMove)
+        );
+        assert_eq!(
+            ChessMove::from_uci("b2b3", &board),
+            Err(UciError::IllegalMove)
+        );
+        assert_eq!(
+            ChessMove::from_uci("e2e", &board),
+            Err(UciError::InvalidUci)
+        );
+    }
+
+    #[test]
+    fn castles() {
+        let fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1";
+        let mut board = Board::from_fen(fen).unwrap();
+        assert!(ChessMove::from_uci("e1g1", &board).is_ok());
+        assert!(ChessMove::from_uci("e1c1", &board).is_ok());
+        assert_eq!(
+            ChessMove::from_uci("e1h1", &board),
+            Err(UciError::IllegalMove)
+        );
+
+        // Chess960 castles are king takes own rook
+        board.set_chess960(true);
+        assert!(ChessMove::from_uci("e1h1", &board).is_ok());
+        assert!(ChessMove::from_uci("e1a1", &board).is_ok());
+        assert_eq!(
+            ChessMove::from_uci("e1g1", &board),
+            Err(UciError::IllegalMove)
+        );
+
+        let board =
+            Board::from_fen("1r2k1r1/pppppppp/8/8/8/8/PPPPPPPP/RK4R1 w GAgb - 0 1").unwrap();
+        assert!(ChessMove::from_uci("b1g1", &board).is_ok());
+        assert!(ChessMove::from_uci("b1a1", &board).is_ok());
+    }
+}